@@ -0,0 +1,184 @@
+#!/usr/bin/env python3
+"""SSZ hash tree root of execution payloads, as committed in beacon blocks.
+
+Generates the expected roots of `should_compute_execution_payload_root` in
+src/block.rs. Without arguments, it prints the roots of the Deneb and
+Capella payloads of that test. With paths, it prints the root of the
+`execution_payload` of each JSON file, in the snake case format of the
+beacon API, such as a relay block submission.
+
+Only the standard library is used.
+"""
+
+import hashlib
+import json
+import sys
+
+BYTES_PER_CHUNK = 32
+MAX_BYTES_PER_TRANSACTION = 2**30
+MAX_TRANSACTIONS_PER_PAYLOAD = 2**20
+MAX_EXTRA_DATA_BYTES = 32
+MAX_WITHDRAWALS_PER_PAYLOAD = 16
+BYTES_PER_LOGS_BLOOM = 256
+
+
+def hash_pair(left, right):
+    return hashlib.sha256(left + right).digest()
+
+
+def merkleize(chunks, limit):
+    """Root of `chunks` padded with zero chunks to the next power of two of
+    `limit`"""
+    depth = max(limit - 1, 0).bit_length()
+    zero_hashes = [bytes(BYTES_PER_CHUNK)]
+    for _ in range(depth):
+        zero_hashes.append(hash_pair(zero_hashes[-1], zero_hashes[-1]))
+
+    layer = list(chunks)
+    if not layer:
+        return zero_hashes[depth]
+    for height in range(depth):
+        if len(layer) % 2:
+            layer.append(zero_hashes[height])
+        layer = [hash_pair(layer[i], layer[i + 1]) for i in range(0, len(layer), 2)]
+
+    return layer[0]
+
+
+def mix_in_length(root, length):
+    return hash_pair(root, length.to_bytes(BYTES_PER_CHUNK, "little"))
+
+
+def pack_bytes(data):
+    data = data + bytes(-len(data) % BYTES_PER_CHUNK)
+    return [data[i : i + BYTES_PER_CHUNK] for i in range(0, len(data), BYTES_PER_CHUNK)]
+
+
+def chunk_limit(max_bytes):
+    return (max_bytes + BYTES_PER_CHUNK - 1) // BYTES_PER_CHUNK
+
+
+def byte_list_root(data, max_bytes):
+    return mix_in_length(merkleize(pack_bytes(data), chunk_limit(max_bytes)), len(data))
+
+
+def uint_root(value, size):
+    return value.to_bytes(size, "little").ljust(BYTES_PER_CHUNK, b"\0")
+
+
+def hex_bytes(value):
+    return bytes.fromhex(value[2:])
+
+
+def address_root(value):
+    return hex_bytes(value).ljust(BYTES_PER_CHUNK, b"\0")
+
+
+def withdrawal_root(withdrawal):
+    fields = [
+        uint_root(int(withdrawal["index"]), 8),
+        uint_root(int(withdrawal["validator_index"]), 8),
+        address_root(withdrawal["address"]),
+        uint_root(int(withdrawal["amount"]), 8),
+    ]
+    return merkleize(fields, len(fields))
+
+
+def execution_payload_root(payload):
+    transactions = [
+        byte_list_root(hex_bytes(txn), MAX_BYTES_PER_TRANSACTION)
+        for txn in payload["transactions"]
+    ]
+    withdrawals = [withdrawal_root(withdrawal) for withdrawal in payload["withdrawals"]]
+
+    fields = [
+        hex_bytes(payload["parent_hash"]),
+        address_root(payload["fee_recipient"]),
+        hex_bytes(payload["state_root"]),
+        hex_bytes(payload["receipts_root"]),
+        merkleize(pack_bytes(hex_bytes(payload["logs_bloom"])), chunk_limit(BYTES_PER_LOGS_BLOOM)),
+        hex_bytes(payload["prev_randao"]),
+        uint_root(int(payload["block_number"]), 8),
+        uint_root(int(payload["gas_limit"]), 8),
+        uint_root(int(payload["gas_used"]), 8),
+        uint_root(int(payload["timestamp"]), 8),
+        byte_list_root(hex_bytes(payload["extra_data"]), MAX_EXTRA_DATA_BYTES),
+        uint_root(int(payload["base_fee_per_gas"]), 32),
+        hex_bytes(payload["block_hash"]),
+        mix_in_length(merkleize(transactions, MAX_TRANSACTIONS_PER_PAYLOAD), len(transactions)),
+        mix_in_length(merkleize(withdrawals, MAX_WITHDRAWALS_PER_PAYLOAD), len(withdrawals)),
+    ]
+    # Deneb
+    if "blob_gas_used" in payload:
+        fields.append(uint_root(int(payload["blob_gas_used"]), 8))
+        fields.append(uint_root(int(payload["excess_blob_gas"]), 8))
+
+    return merkleize(fields, len(fields))
+
+
+# Payloads of the test: the headers of mainnet blocks 19449567 and 18677559,
+# with the type 2 and legacy transaction fixtures
+DENEB = {
+    "parent_hash": "0x90926e0298d418181bd20c23b332451e35fd7d696b5dcdc5a3a0a6b715f4c717",
+    "fee_recipient": "0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5",
+    "state_root": "0x707875120a7103621fb4131df59904cda39de948dfda9084a1e3da44594d5404",
+    "receipts_root": "0xd43aa19ecb03571d1b86d89d9bb980139d32f2f2ba59646cd5c1de9e80c68c90",
+    "logs_bloom": "0xc36919406572730518285284f2293101104140c0d42c4a786c892467868a8806f40159d29988002870403902413a1d04321320308da2e845438429e0012a00b419d8ccc8584a1c28f82a415d04eab8a5ae75c00d07761acf233414c08b6d9b571c06156086c70ea5186e9b989b0c2d55c0213c936805cd2ab331589c90194d070c00867549b1e1be14cb24500b0386cd901197c1ef5a00da453234fa48f3003dcaa894e3111c22b80e17f7d4388385a10720cda1140c0400f9e084ca34fc4870fb16b472340a2a6a63115a82522f506c06c2675080508834828c63defd06bc2331b4aa708906a06a560457b114248041e40179ebc05c6846c1e922125982f427",
+    "prev_randao": "0x4c068e902990f21f92a2456fc75c59bec8be03b7f13682b6ebd27da56269beb5",
+    "block_number": "19449567",
+    "gas_limit": "30000000",
+    "gas_used": "11535164",
+    "timestamp": "1710617795",
+    "extra_data": "0x6265617665726275696c642e6f7267",
+    "base_fee_per_gas": "36619559341",
+    "block_hash": "0x85cdcbe36217fd57bf2c33731d8460657a7ce512401f49c9f6392c82a7ccf7ac",
+    "transactions": [
+        "0x02f9023401830272d280850e52fc744a8303851c94a69babef1ca67a37ffaf7a485dfff3382056e78c83b4c500b901c478e111f60000000000000000000000002d876e69e7017421b77822b1bb4c8da1307a19700000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000014470aa0dfe000000000000000000000000e45b4a84e0ad24b8617a489d743c52b84b7acebe0000000000000000000000005b7533812759b45c2b44c19e320ba2cd2681b542000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000002c6b50bca00000000000000000000000000000000000000000000000000006c72001c8d6e00000000000000000000000000000000000000000000000001a5ce878dc1dc50000000000000000000000000000000000000000000013633fa3aece210000000000000000000000000000000000000000000000000013633fa3aece2100000000000000000000000000000000000000000000000000000000000000065673bffff0000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000c001a0bed4a3918a4478c26dc5cec677fe21dc3599f2597e2b8ff0320c141a1d5213c8a055a57e4e2904c8268c698357e1c77789af9d484122ace41bb69add4f3bc697c0",
+        "0xf8938206d88545d964b800830160a5941643e812ae58766192cf7d2cf9567df2c37e9b7f8829a2241af62c0000a4a1903eab00000000000000000000000000000000000000000000000000000000000000002da0b1df344bc5f8d4508b03bc24e73b8a411e6662152fc083bc044e59826cae3421a008d15757b321670c81ad46e61eaa7c58279559af972d048648cfc40ba8ff4133",
+    ],
+    "withdrawals": [
+        {"address": "0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f", "amount": "18226960", "index": "38216111", "validator_index": "1005753"},
+        {"address": "0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f", "amount": "18300000", "index": "38216112", "validator_index": "1005754"},
+    ],
+    "blob_gas_used": "0",
+    "excess_blob_gas": "0",
+}
+
+CAPELLA = {
+    "parent_hash": "0x9e8dd74d00937fddbbf465cb828acbdb9af2514a6e9d633589f5e4a047dfec5b",
+    "fee_recipient": "0x95222290dd7278aa3ddd389cc1e1d165cc4bafe5",
+    "state_root": "0xf7f5ceaac85a1ecd7e0c74f6af0cc2d2a88aca9ab9e356c12d1670322ec7fbdd",
+    "receipts_root": "0xda79a01eae58b7437540a7647a6e8c1d26109bc5d985e6ba315cf7637c785d41",
+    "logs_bloom": "0xa8b0050247c27195101a00008040cea31c210a20908a52153201006004103c730c04a509281890a083690d621000c1884319910a893124c987400e4886328c22bc9281d8202c0a092c954029e4546aa990230815045e2804a0101470cba8144050100100b16cc2998c98438800263cc424182a7280031700172414f6c18a800c4812048003000144005b046ad900800e4b055205116480c82401404276151569120c0849013820616a1020c018821cb880c1540200618d0200380e9a041830181ea4340a029018406000a00000f110e0040c13278503009058406f0e800122462454a62029c1806ca24410700800088480819820009600430008809108052005",
+    "prev_randao": "0xf380df736ba8959509e0214cdf0862db0f45731d950789a2780a821faabc15a8",
+    "block_number": "18677559",
+    "gas_limit": "30000000",
+    "gas_used": "11754067",
+    "timestamp": "1701264383",
+    "extra_data": "0x6265617665726275696c642e6f7267",
+    "base_fee_per_gas": "41014545799",
+    "block_hash": "0x8c07fbc176e8cd1b0ea49dc56132e6e571d0c94ef0b88907658c7d197c4a9dfc",
+    "transactions": [
+        "0x02f9023401830272d280850e52fc744a8303851c94a69babef1ca67a37ffaf7a485dfff3382056e78c83b4c500b901c478e111f60000000000000000000000002d876e69e7017421b77822b1bb4c8da1307a19700000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000014470aa0dfe000000000000000000000000e45b4a84e0ad24b8617a489d743c52b84b7acebe0000000000000000000000005b7533812759b45c2b44c19e320ba2cd2681b542000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000002c6b50bca00000000000000000000000000000000000000000000000000006c72001c8d6e00000000000000000000000000000000000000000000000001a5ce878dc1dc50000000000000000000000000000000000000000000013633fa3aece210000000000000000000000000000000000000000000000000013633fa3aece2100000000000000000000000000000000000000000000000000000000000000065673bffff0000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000c001a0bed4a3918a4478c26dc5cec677fe21dc3599f2597e2b8ff0320c141a1d5213c8a055a57e4e2904c8268c698357e1c77789af9d484122ace41bb69add4f3bc697c0",
+        "0xf8938206d88545d964b800830160a5941643e812ae58766192cf7d2cf9567df2c37e9b7f8829a2241af62c0000a4a1903eab00000000000000000000000000000000000000000000000000000000000000002da0b1df344bc5f8d4508b03bc24e73b8a411e6662152fc083bc044e59826cae3421a008d15757b321670c81ad46e61eaa7c58279559af972d048648cfc40ba8ff4133",
+    ],
+    "withdrawals": [
+        {"address": "0xb9d7934878b5fb9610b3fe8a5e441e8fad7e293f", "amount": "16772225", "index": "26008521", "validator_index": "417862"},
+    ],
+}
+
+
+def main():
+    if len(sys.argv) == 1:
+        print("deneb   0x" + execution_payload_root(DENEB).hex())
+        print("capella 0x" + execution_payload_root(CAPELLA).hex())
+        return
+
+    for path in sys.argv[1:]:
+        with open(path) as file:
+            payload = json.load(file)["execution_payload"]
+        print(path, "0x" + execution_payload_root(payload).hex())
+
+
+if __name__ == "__main__":
+    main()
@@ -1,13 +1,44 @@
 //! A block representing an Ethereum block
-use alloy_primitives::{keccak256, Address, BlockHash, Bloom, Bytes, B256, B64, U256, U64};
-use alloy_rlp::{Encodable, RlpDecodable, RlpEncodable};
-use alloy_trie::{HashBuilder, Nibbles};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::OnceLock,
+};
+
+use alloy_primitives::{b256, Address, BlockHash, Bloom, Bytes, Log, TxHash, B256, B64, U256, U64};
+use alloy_rlp::{Decodable, Encodable, RlpDecodable, RlpEncodable};
+use alloy_trie::Nibbles;
 use ethers::{prelude, types::TransactionReceipt};
 
-use crate::{transaction::VerifiedTransaction, utils::index_for_rlp};
+use crate::{
+    error::{
+        ChainError, DecodeError, ProofError, RangeError, ReceiptError, TransactionError,
+        VerifyError,
+    },
+    hasher::keccak256,
+    proof::verify_proof,
+    receipt::VerifiedReceipt,
+    transaction::{Tx4844, VerifiedTransaction, BYTES_PER_BLOB, GAS_PER_BLOB},
+    trie::{ordered_trie_proofs, ordered_trie_root},
+    utils::{
+        decode_optional, ensure_consumed, ensure_no_trailing_bytes, index_for_rlp,
+        take_list_payload,
+    },
+};
+
+/// Generations an uncle can be behind the block including it
+pub const MAX_UNCLE_DEPTH: u64 = 6;
+
+/// Hash of an empty list of uncles, `keccak256(rlp([]))`. It is the
+/// `uncles_hash` of every block after the merge.
+pub const EMPTY_UNCLES_HASH: BlockHash =
+    b256!("1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347");
 
 /// Ethereum block hader
-#[derive(Debug, RlpDecodable, RlpEncodable)]
+///
+/// Fields introduced by later forks are optional and, when missing, are not
+/// encoded.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable)]
+#[rlp(trailing)]
 pub struct BlockHeader {
     pub parent: BlockHash,
     pub uncles_hash: BlockHash,
@@ -24,30 +55,306 @@ pub struct BlockHeader {
     pub extra_data: Bytes,
     pub mix_hash: B256,
     pub nonce: B64,
-    pub base_fee_per_gas: U256,
-    pub withdrawals_root: B256,
+    pub base_fee_per_gas: Option<U256>,
+    pub withdrawals_root: Option<B256>,
+    pub blob_gas_used: Option<U64>,
+    pub excess_blob_gas: Option<U64>,
+    pub parent_beacon_block_root: Option<B256>,
+    pub requests_hash: Option<B256>,
+}
+
+impl BlockHeader {
+    /// Hash of the header
+    pub fn hash(&self) -> BlockHash {
+        let mut buffer = Vec::<u8>::new();
+        self.encode(&mut buffer);
+
+        keccak256(buffer)
+    }
+
+    /// Decode a header from its RLP encoding, as sent in `BlockHeaders`
+    /// messages. Fields of later forks are read only when present.
+    ///
+    /// Bytes after the header fail with [DecodeError::TrailingBytes].
+    pub fn decode_rlp(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (header, rest) = Self::decode_rlp_lenient(bytes)?;
+        ensure_no_trailing_bytes(rest)?;
+
+        Ok(header)
+    }
+
+    /// Decode a header as [Self::decode_rlp], returning the bytes that
+    /// follow it
+    pub fn decode_rlp_lenient(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeError> {
+        let mut buf = bytes;
+        let header = <Self as Decodable>::decode(&mut buf)?;
+
+        Ok((header, buf))
+    }
+
+    /// Whether the block follows the merge, which set the difficulty to zero
+    /// (EIP-3675). Proof of work checks don't apply to these blocks
+    pub fn is_post_merge(&self) -> bool {
+        self.difficulty.is_zero()
+    }
+
+    /// Share of the gas limit used by the block, from 0 to 1, and 0 without
+    /// gas limit. Gas values fit in 64 bits, and are exact as `f64` below
+    /// 2^53
+    pub fn gas_utilization(&self) -> f64 {
+        let gas_limit = self.gas_limit.saturating_to::<u64>();
+        if gas_limit == 0 {
+            return 0.0;
+        }
+
+        self.gas_used.saturating_to::<u64>() as f64 / gas_limit as f64
+    }
+
+    /// Whether the header can be included as an uncle of `including_block`:
+    /// it is at most [MAX_UNCLE_DEPTH] blocks behind, and the child of one of
+    /// the `ancestors` of the including block without being one of them.
+    ///
+    /// `ancestors` are trusted to be the chain of `including_block`, as
+    /// checked by [verify_header_chain].
+    pub fn is_valid_uncle(&self, including_block: &BlockHeader, ancestors: &[BlockHeader]) -> bool {
+        let number = self.number.to::<u64>();
+        let including_number = including_block.number.to::<u64>();
+        if number >= including_number || including_number - number > MAX_UNCLE_DEPTH {
+            return false;
+        }
+
+        let hash = self.hash();
+        if hash == including_block.hash() || ancestors.iter().any(|header| header.hash() == hash) {
+            return false;
+        }
+
+        ancestors.iter().any(|parent| {
+            parent.hash() == self.parent && parent.number + U64::from(1) == self.number
+        })
+    }
+
+    /// Root of the parent beacon block (EIP-4788), from Cancun.
+    ///
+    /// Before the transactions, the root is stored in the beacon roots
+    /// contract, `0x000F3df6D732807Ef1319fB7B8bB8522d0Beac02`, keyed by the
+    /// timestamp of the block. Contracts read it from there.
+    pub fn parent_beacon_block_root(&self) -> Option<B256> {
+        self.parent_beacon_block_root
+    }
+
+    /// Infer the fork of the block from the optional fields it has, as for a
+    /// header of an unknown chain. See [Self::fork_from_schedule] for the
+    /// fork activated on a known chain.
+    pub fn fork_from_fields(&self) -> Fork {
+        if self.requests_hash.is_some() {
+            Fork::Prague
+        } else if self.parent_beacon_block_root.is_some() {
+            Fork::Cancun
+        } else if self.withdrawals_root.is_some() {
+            Fork::Shanghai
+        } else if self.base_fee_per_gas.is_some() {
+            if self.is_post_merge() {
+                Fork::Paris
+            } else {
+                Fork::London
+            }
+        } else {
+            Fork::PreLondon
+        }
+    }
+
+    /// Fork of the block on a chain following `schedule`. After the merge,
+    /// forks scheduled by time are found from the timestamp.
+    pub fn fork_from_schedule(&self, schedule: &ForkSchedule) -> Fork {
+        let fork = Fork::from_block_number(self.number.to(), schedule);
+        if fork < Fork::Paris {
+            return fork;
+        }
+
+        fork.max(Fork::from_timestamp(
+            self.timestamp.saturating_to(),
+            schedule,
+        ))
+    }
+
+    /// Encode the header with the fields it has up to `fork`. Fields
+    /// introduced by later forks are not encoded.
+    pub fn encode_for_fork(&self, fork: Fork, out: &mut dyn alloy_rlp::BufMut) {
+        let mut header = self.clone();
+        if fork < Fork::London {
+            header.base_fee_per_gas = None;
+        }
+        if fork < Fork::Shanghai {
+            header.withdrawals_root = None;
+        }
+        if fork < Fork::Cancun {
+            header.blob_gas_used = None;
+            header.excess_blob_gas = None;
+            header.parent_beacon_block_root = None;
+        }
+        if fork < Fork::Prague {
+            header.requests_hash = None;
+        }
+
+        header.encode(out);
+    }
+}
+
+impl Decodable for BlockHeader {
+    /// Decode the fields of later forks only when present. A zero, as the
+    /// `blob_gas_used` of a block without blobs, is still a present field.
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let mut payload = take_list_payload(buf)?;
+
+        let header = Self {
+            parent: Decodable::decode(&mut payload)?,
+            uncles_hash: Decodable::decode(&mut payload)?,
+            miner: Decodable::decode(&mut payload)?,
+            state_root: Decodable::decode(&mut payload)?,
+            transaction_root: Decodable::decode(&mut payload)?,
+            receipts_root: Decodable::decode(&mut payload)?,
+            logs_bloom: Decodable::decode(&mut payload)?,
+            difficulty: Decodable::decode(&mut payload)?,
+            number: Decodable::decode(&mut payload)?,
+            gas_limit: Decodable::decode(&mut payload)?,
+            gas_used: Decodable::decode(&mut payload)?,
+            timestamp: Decodable::decode(&mut payload)?,
+            extra_data: Decodable::decode(&mut payload)?,
+            mix_hash: Decodable::decode(&mut payload)?,
+            nonce: Decodable::decode(&mut payload)?,
+            base_fee_per_gas: decode_optional(&mut payload)?,
+            withdrawals_root: decode_optional(&mut payload)?,
+            blob_gas_used: decode_optional(&mut payload)?,
+            excess_blob_gas: decode_optional(&mut payload)?,
+            parent_beacon_block_root: decode_optional(&mut payload)?,
+            requests_hash: decode_optional(&mut payload)?,
+        };
+        ensure_consumed(payload)?;
+
+        Ok(header)
+    }
 }
 
-impl<T> From<&prelude::Block<T>> for BlockHeader {
-    fn from(value: &prelude::Block<T>) -> Self {
+/// Forks that changed the fields of the header
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Fork {
+    PreLondon,
+    /// Adds `base_fee_per_gas`
+    London,
+    /// The merge, blocks have no difficulty
+    Paris,
+    /// Adds `withdrawals_root`
+    Shanghai,
+    /// Adds `blob_gas_used`, `excess_blob_gas` and `parent_beacon_block_root`
+    Cancun,
+    /// Adds `requests_hash`
+    Prague,
+}
+
+impl Fork {
+    /// Fork active at block `number` on a chain following `schedule`
+    pub fn from_block_number(number: u64, schedule: &ForkSchedule) -> Self {
+        schedule
+            .blocks
+            .iter()
+            .filter(|(_, activation)| **activation <= number)
+            .map(|(fork, _)| *fork)
+            .max()
+            .unwrap_or(Fork::PreLondon)
+    }
+
+    /// Fork active at `timestamp` on a chain following `schedule`, for
+    /// post-merge blocks. Forks scheduled by time follow the merge: before
+    /// the first of them, the block is in Paris.
+    pub fn from_timestamp(timestamp: u64, schedule: &ForkSchedule) -> Self {
+        schedule
+            .timestamps
+            .iter()
+            .filter(|(_, activation)| **activation <= timestamp)
+            .map(|(fork, _)| *fork)
+            .max()
+            .unwrap_or(Fork::Paris)
+    }
+}
+
+/// Activation of the forks of a chain. Forks up to the merge activate at a
+/// block number, later ones at a timestamp. Forks missing from the schedule
+/// are not active.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ForkSchedule {
+    pub blocks: BTreeMap<Fork, u64>,
+    pub timestamps: BTreeMap<Fork, u64>,
+}
+
+impl ForkSchedule {
+    /// Forks of Ethereum mainnet
+    pub fn mainnet() -> Self {
         Self {
+            blocks: BTreeMap::from([(Fork::London, 12_965_000), (Fork::Paris, 15_537_394)]),
+            timestamps: BTreeMap::from([
+                (Fork::Shanghai, 1_681_338_455),
+                (Fork::Cancun, 1_710_338_135),
+                (Fork::Prague, 1_746_612_311),
+            ]),
+        }
+    }
+}
+
+impl<T> TryFrom<&prelude::Block<T>> for BlockHeader {
+    type Error = TransactionError;
+
+    /// Fields that a pending block may leave null are rejected as missing
+    fn try_from(value: &prelude::Block<T>) -> Result<Self, Self::Error> {
+        let missing = |field| TransactionError::MissingField { field };
+
+        Ok(Self {
             parent: BlockHash::new(value.parent_hash.0),
             uncles_hash: BlockHash::new(value.uncles_hash.0),
-            miner: Address::new(value.author.unwrap().0),
+            miner: Address::new(value.author.ok_or(missing("miner"))?.0),
             state_root: B256::new(value.state_root.0),
             transaction_root: B256::new(value.transactions_root.0),
             receipts_root: B256::new(value.receipts_root.0),
-            logs_bloom: Bloom::new(value.logs_bloom.unwrap().0),
+            logs_bloom: Bloom::new(value.logs_bloom.ok_or(missing("logsBloom"))?.0),
             difficulty: value.difficulty.into(),
-            number: U64::from_limbs(value.number.unwrap().0),
+            number: U64::from_limbs(value.number.ok_or(missing("number"))?.0),
             gas_limit: value.gas_limit.into(),
             gas_used: value.gas_used.into(),
             timestamp: value.timestamp.into(),
             extra_data: Bytes::from(value.extra_data.0.clone()),
-            mix_hash: B256::new(value.mix_hash.unwrap().0),
-            nonce: B64::new(value.nonce.unwrap().0),
-            base_fee_per_gas: value.base_fee_per_gas.unwrap().into(),
-            withdrawals_root: B256::new(value.withdrawals_root.unwrap().0),
+            mix_hash: B256::new(value.mix_hash.ok_or(missing("mixHash"))?.0),
+            nonce: B64::new(value.nonce.ok_or(missing("nonce"))?.0),
+            base_fee_per_gas: value.base_fee_per_gas.map(Into::into),
+            withdrawals_root: value.withdrawals_root.map(|root| B256::new(root.0)),
+            blob_gas_used: value.blob_gas_used.map(|gas| U64::from(gas.as_u64())),
+            excess_blob_gas: value.excess_blob_gas.map(|gas| U64::from(gas.as_u64())),
+            parent_beacon_block_root: value.parent_beacon_block_root.map(|root| B256::new(root.0)),
+            // Not known by ethers, it ends up in `other`
+            requests_hash: value
+                .other
+                .get_deserialized::<ethers::types::H256>("requestsHash")
+                .and_then(Result::ok)
+                .map(|hash| B256::new(hash.0)),
+        })
+    }
+}
+
+/// Withdrawal of a validator from the beacon chain (EIP-4895)
+#[derive(Debug, Clone, PartialEq, Eq, RlpDecodable, RlpEncodable)]
+pub struct Withdrawal {
+    pub index: u64,
+    pub validator_index: u64,
+    pub address: Address,
+    /// Amount in gwei
+    pub amount: u64,
+}
+
+impl From<&ethers::types::Withdrawal> for Withdrawal {
+    fn from(value: &ethers::types::Withdrawal) -> Self {
+        Self {
+            index: value.index.as_u64(),
+            validator_index: value.validator_index.as_u64(),
+            address: Address::new(value.address.0),
+            amount: value.amount.as_u64(),
         }
     }
 }
@@ -57,38 +364,596 @@ pub struct VerifiedBlock {
     pub hash: BlockHash,
     pub header: BlockHeader,
     pub transactions: Vec<VerifiedTransaction>,
+    /// Withdrawals of the block, from Shanghai
+    pub withdrawals: Option<Vec<Withdrawal>>,
+    /// Hashes of `transactions`, computed on first use
+    transaction_hashes: OnceLock<Vec<TxHash>>,
 }
 
 impl VerifiedBlock {
     pub fn new(
         block: &prelude::Block<ethers::types::Transaction>,
         receipts: &[TransactionReceipt],
-    ) -> Self {
+    ) -> Result<Self, TransactionError> {
         // Maybe `receipts` are not ordered by transaction index
         let mut receipts = receipts.to_owned();
         receipts.sort_by(|a, b| a.transaction_index.cmp(&b.transaction_index));
 
         let transactions = std::iter::zip(block.transactions.iter(), receipts.iter())
             .map(|(txn, receipt)| VerifiedTransaction::new(txn, receipt))
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        let header = BlockHeader::from(block);
+        Self::from_ethers(block, transactions)
+    }
 
-        let mut verified_block = Self {
-            header,
-            hash: BlockHash::ZERO,
-            transactions,
-        };
+    /// Build a block when its receipts are not known. The transactions root
+    /// can be verified, the receipts of the transactions are left empty.
+    pub fn new_without_receipts(
+        block: &prelude::Block<ethers::types::Transaction>,
+    ) -> Result<Self, TransactionError> {
+        let transactions = block
+            .transactions
+            .iter()
+            .map(VerifiedTransaction::new_without_receipt)
+            .collect::<Result<_, _>>()?;
+
+        Self::from_ethers(block, transactions)
+    }
+
+    fn from_ethers(
+        block: &prelude::Block<ethers::types::Transaction>,
+        transactions: Vec<VerifiedTransaction>,
+    ) -> Result<Self, TransactionError> {
+        let header = BlockHeader::try_from(block)?;
+
+        let mut verified_block = Self::from_parts(header, transactions);
+        verified_block.withdrawals = block
+            .withdrawals
+            .as_ref()
+            .map(|withdrawals| withdrawals.iter().map(Withdrawal::from).collect());
 
         // Calculate transaction trie and update the header
         verified_block.header.transaction_root = verified_block.transaction_trie();
 
         // Calculate block hash
-        let mut buffer = Vec::<u8>::new();
-        verified_block.header.encode(&mut buffer);
-        verified_block.hash = keccak256(buffer);
+        verified_block.hash = verified_block.header.hash();
+
+        Ok(verified_block)
+    }
+
+    /// Build a block from its header and transactions, as they are
+    pub fn from_parts(header: BlockHeader, transactions: Vec<VerifiedTransaction>) -> Self {
+        Self {
+            hash: header.hash(),
+            header,
+            transactions,
+            withdrawals: None,
+            transaction_hashes: OnceLock::new(),
+        }
+    }
+
+    /// Hashes of the transactions, in block order
+    fn cached_transaction_hashes(&self) -> &[TxHash] {
+        self.transaction_hashes
+            .get_or_init(|| self.transactions.iter().map(|txn| txn.hash()).collect())
+    }
+
+    /// Hashes of the transactions, in block order, as returned by
+    /// `eth_getBlockByHash` without full transactions
+    pub fn transaction_hashes(&self) -> Vec<TxHash> {
+        self.cached_transaction_hashes().to_vec()
+    }
+
+    /// Get the transaction at `index`
+    pub fn transaction_by_index(&self, index: usize) -> Option<&VerifiedTransaction> {
+        self.transactions.get(index)
+    }
+
+    /// Get the transaction with the given hash, together with its index
+    pub fn transaction_by_hash(&self, hash: &TxHash) -> Option<(usize, &VerifiedTransaction)> {
+        let index = self
+            .cached_transaction_hashes()
+            .iter()
+            .position(|txn_hash| txn_hash == hash)?;
+
+        Some((index, &self.transactions[index]))
+    }
+
+    /// Index of `transaction` in the block, as needed to request its proof
+    pub fn index_of_transaction(&self, transaction: &VerifiedTransaction) -> Option<usize> {
+        let hash = transaction.hash();
+
+        self.cached_transaction_hashes()
+            .iter()
+            .position(|txn_hash| txn_hash == &hash)
+    }
+
+    /// Amount withdrawn in the block, in gwei
+    pub fn total_withdrawn(&self) -> u64 {
+        self.withdrawals
+            .iter()
+            .flatten()
+            .map(|withdrawal| withdrawal.amount)
+            .sum()
+    }
+
+    /// Wei sent by the transactions of the block, not counting internal
+    /// transfers. The sum can't realistically overflow, it saturates if it
+    /// does
+    pub fn total_value_transferred(&self) -> U256 {
+        self.transactions.iter().fold(U256::ZERO, |total, txn| {
+            total.checked_add(txn.value()).unwrap_or(U256::MAX)
+        })
+    }
+
+    /// SSZ hash tree root of the execution payload header of the block, as
+    /// committed in the beacon block.
+    ///
+    /// Unlike the header, the payload commits to the SSZ roots of the
+    /// transactions and withdrawals, hence the whole block is needed. `None`
+    /// before the merge, or if the block misses its withdrawals after Shanghai.
+    #[cfg(feature = "beacon")]
+    pub fn execution_payload_root(&self) -> Option<B256> {
+        use crate::ssz::{
+            address_root, byte_list_root, merkleize, mix_in_length, pack_bytes, u256_root,
+            u64_root, MAX_BYTES_PER_TRANSACTION, MAX_EXTRA_DATA_BYTES,
+            MAX_TRANSACTIONS_PER_PAYLOAD, MAX_WITHDRAWALS_PER_PAYLOAD,
+        };
+
+        let header = &self.header;
+        let base_fee = header.base_fee_per_gas?;
+        if !header.difficulty.is_zero() || header.extra_data.len() > MAX_EXTRA_DATA_BYTES {
+            return None;
+        }
+
+        let transactions: Vec<B256> = self
+            .transactions
+            .iter()
+            .map(|txn| byte_list_root(&alloy_rlp::encode(txn), MAX_BYTES_PER_TRANSACTION))
+            .collect();
+
+        let mut fields = vec![
+            header.parent,
+            address_root(header.miner),
+            header.state_root,
+            header.receipts_root,
+            merkleize(&pack_bytes(header.logs_bloom.as_slice()), 8),
+            header.mix_hash,
+            u64_root(header.number.to()),
+            u64_root(header.gas_limit.try_into().ok()?),
+            u64_root(header.gas_used.try_into().ok()?),
+            u64_root(header.timestamp.try_into().ok()?),
+            byte_list_root(&header.extra_data, MAX_EXTRA_DATA_BYTES),
+            u256_root(base_fee),
+            self.hash,
+            mix_in_length(
+                merkleize(&transactions, MAX_TRANSACTIONS_PER_PAYLOAD),
+                transactions.len(),
+            ),
+        ];
+
+        // Capella
+        if header.withdrawals_root.is_some() {
+            let withdrawals: Vec<B256> = self
+                .withdrawals
+                .as_ref()?
+                .iter()
+                .map(|withdrawal| {
+                    merkleize(
+                        &[
+                            u64_root(withdrawal.index),
+                            u64_root(withdrawal.validator_index),
+                            address_root(withdrawal.address),
+                            u64_root(withdrawal.amount),
+                        ],
+                        4,
+                    )
+                })
+                .collect();
+
+            fields.push(mix_in_length(
+                merkleize(&withdrawals, MAX_WITHDRAWALS_PER_PAYLOAD),
+                withdrawals.len(),
+            ));
+        }
+
+        // Deneb
+        if let (Some(blob_gas_used), Some(excess_blob_gas)) =
+            (header.blob_gas_used, header.excess_blob_gas)
+        {
+            fields.push(u64_root(blob_gas_used.to()));
+            fields.push(u64_root(excess_blob_gas.to()));
+        }
+
+        Some(merkleize(&fields, fields.len()))
+    }
+
+    /// Check that `withdrawals_root` of the header is the root of the
+    /// withdrawals. Blocks before Shanghai have neither.
+    pub fn verify_withdrawals_root(&self) -> bool {
+        let withdrawals_root = self
+            .withdrawals
+            .as_ref()
+            .map(|withdrawals| ordered_trie_root(withdrawals));
+
+        self.header.withdrawals_root == withdrawals_root
+    }
+
+    /// Check that the indices of the withdrawals increase by one across the
+    /// block, as the beacon chain assigns them
+    pub fn verify_withdrawal_ordering(&self) -> bool {
+        self.withdrawals.iter().all(|withdrawals| {
+            withdrawals
+                .windows(2)
+                .all(|pair| pair[0].index.checked_add(1) == Some(pair[1].index))
+        })
+    }
+
+    /// Versioned hashes of the blobs of the block, in transaction order
+    pub fn blob_versioned_hashes(&self) -> Vec<B256> {
+        self.blob_transactions()
+            .flat_map(|txn| txn.blob_versioned_hashes.iter().copied())
+            .collect()
+    }
+
+    /// Size in bytes of the RLP encoding of the block body, as sent in
+    /// `BlockBodies` messages: the transactions in their body form, the
+    /// uncles and the withdrawals when present.
+    ///
+    /// Uncles are not kept by the block, their list is counted as empty, as
+    /// it is after the merge.
+    pub fn encoded_body_size(&self) -> usize {
+        // Lists and byte strings have headers of the same size
+        let with_header =
+            |payload_length| alloy_rlp::length_of_length(payload_length) + payload_length;
+
+        // Typed transactions are wrapped in a byte string
+        let transactions = self
+            .transactions
+            .iter()
+            .map(|txn| match txn {
+                VerifiedTransaction::Legacy(_) => txn.length(),
+                _ => with_header(txn.length()),
+            })
+            .sum();
 
-        verified_block
+        let mut payload_length = with_header(transactions);
+        payload_length += with_header(0);
+        if let Some(withdrawals) = &self.withdrawals {
+            payload_length += withdrawals.length();
+        }
+
+        with_header(payload_length)
+    }
+
+    /// Hash of the RLP encoded transactions list of the block body, with the
+    /// transactions in their body form. Unlike the transactions root, it is
+    /// not a trie
+    pub fn transactions_list_hash(&self) -> B256 {
+        let mut items = Vec::<u8>::new();
+        for txn in &self.transactions {
+            txn.encode_for_body(&mut items);
+        }
+
+        let mut list = Vec::<u8>::new();
+        alloy_rlp::Header {
+            list: true,
+            payload_length: items.len(),
+        }
+        .encode(&mut list);
+        list.extend(items);
+
+        keccak256(list)
+    }
+
+    /// Number of blobs carried by the block
+    pub fn blob_count(&self) -> usize {
+        self.blob_transactions()
+            .map(|txn| txn.blob_versioned_hashes.len())
+            .sum()
+    }
+
+    /// Blob transactions of the block
+    fn blob_transactions(&self) -> impl Iterator<Item = &Tx4844> {
+        self.transactions.iter().filter_map(|txn| match txn {
+            VerifiedTransaction::Eip4844(txn) => Some(txn),
+            _ => None,
+        })
+    }
+
+    /// Blob gas used by the blob transactions of the block
+    pub fn total_blob_gas(&self) -> u64 {
+        self.blob_count() as u64 * GAS_PER_BLOB
+    }
+
+    /// Data the block makes available: the calldata of its transactions and
+    /// its blobs, to estimate the data availability cost of a rollup
+    pub fn da_footprint(&self) -> DaFootprint {
+        let blob_count = self.blob_count();
+
+        DaFootprint {
+            calldata_bytes: self.transactions.iter().map(|txn| txn.data().len()).sum(),
+            blob_count,
+            blob_bytes: blob_count * BYTES_PER_BLOB,
+        }
+    }
+
+    /// Check that `blob_gas_used` of the header matches the blobs of the
+    /// transactions. Blocks before Cancun must not contain blobs.
+    pub fn verify_blob_gas_used(&self) -> bool {
+        let blob_gas_used = self.header.blob_gas_used.unwrap_or(U64::ZERO);
+
+        blob_gas_used == U64::from(self.total_blob_gas())
+    }
+
+    /// Gas used by each transaction, from the cumulative gas of the receipts.
+    /// Fails if the cumulative gas decreases, as for out of order receipts
+    pub fn per_transaction_gas(&self) -> Result<Vec<U256>, ReceiptError> {
+        let mut previous = U256::ZERO;
+
+        self.transactions
+            .iter()
+            .enumerate()
+            .map(|(index, txn)| {
+                let cumulative_gas_used = txn.receipt().cumulative_gas_used;
+                let gas_used = cumulative_gas_used
+                    .checked_sub(previous)
+                    .ok_or(ReceiptError::CumulativeGasNotIncreasing { index })?;
+                previous = cumulative_gas_used;
+
+                Ok(gas_used)
+            })
+            .collect()
+    }
+
+    /// Check that the cumulative gas used increases with every receipt, as each
+    /// transaction uses at least 21000 gas
+    pub fn verify_cumulative_gas_monotonic(&self) -> Result<(), ReceiptError> {
+        let mut previous = U256::ZERO;
+
+        for (index, txn) in self.transactions.iter().enumerate() {
+            let cumulative_gas_used = txn.receipt().cumulative_gas_used;
+            if cumulative_gas_used <= previous {
+                return Err(ReceiptError::CumulativeGasNotIncreasing { index });
+            }
+            previous = cumulative_gas_used;
+        }
+
+        Ok(())
+    }
+
+    /// Verify the receipts against the header: the receipts trie, the
+    /// cumulative gas used, the gas used of the block and the logs bloom
+    pub fn verify_receipts(&self) -> Result<(), ReceiptError> {
+        if self.receipt_trie() != self.header.receipts_root {
+            return Err(ReceiptError::ReceiptsRootMismatch);
+        }
+
+        self.verify_gas_used()?;
+        self.verify_logs_bloom()
+    }
+
+    /// Check that each receipt is typed as its transaction. Receipts of
+    /// legacy transactions may be untyped or typed 0
+    pub fn verify_receipt_type_consistency(&self) -> Result<(), ReceiptError> {
+        for (index, txn) in self.transactions.iter().enumerate() {
+            let receipt_type = txn
+                .receipt()
+                .transaction_type
+                .filter(|tx_type| *tx_type != 0);
+            if receipt_type != txn.transaction_type() {
+                return Err(ReceiptError::TypeMismatch { index });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check the cumulative gas used of the receipts, and that the last one
+    /// is the gas used of the block
+    fn verify_gas_used(&self) -> Result<(), ReceiptError> {
+        self.verify_cumulative_gas_monotonic()?;
+
+        let receipts_gas = self
+            .transactions
+            .last()
+            .map_or(U256::ZERO, |txn| txn.receipt().cumulative_gas_used);
+        if receipts_gas != self.header.gas_used {
+            return Err(ReceiptError::GasUsedMismatch {
+                header: self.header.gas_used,
+                receipts: receipts_gas,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Check that the logs bloom of the header is the union of the receipts
+    /// ones
+    fn verify_logs_bloom(&self) -> Result<(), ReceiptError> {
+        let mut logs_bloom = Bloom::ZERO;
+        for txn in &self.transactions {
+            logs_bloom.accrue_bloom(&txn.receipt().logs_bloom);
+        }
+        if logs_bloom != self.header.logs_bloom {
+            return Err(ReceiptError::LogsBloomMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Run every check of the block against its header, without stopping at
+    /// the first failure, to report all of them
+    pub fn explain_verification(&self) -> VerificationReport {
+        fn check(error: Option<String>) -> Check {
+            Check {
+                passed: error.is_none(),
+                message: error.unwrap_or_else(|| "ok".to_owned()),
+            }
+        }
+
+        let uncles_valid =
+            !self.header.difficulty.is_zero() || self.header.uncles_hash == EMPTY_UNCLES_HASH;
+
+        VerificationReport {
+            hash: check(
+                (self.header.hash() != self.hash)
+                    .then(|| VerifyError::BlockHashMismatch.to_string()),
+            ),
+            transaction_root: check(
+                (self.transaction_trie() != self.header.transaction_root)
+                    .then(|| VerifyError::TransactionRootMismatch.to_string()),
+            ),
+            receipts_root: check(
+                (self.receipt_trie() != self.header.receipts_root)
+                    .then(|| VerifyError::ReceiptsRootMismatch.to_string()),
+            ),
+            gas: check(self.verify_gas_used().err().map(|error| error.to_string())),
+            bloom: check(
+                self.verify_logs_bloom()
+                    .err()
+                    .map(|error| error.to_string()),
+            ),
+            uncles: check((!uncles_valid).then(|| VerifyError::UnclesHashMismatch.to_string())),
+            withdrawals: check(
+                (!self.verify_withdrawals_root())
+                    .then(|| VerifyError::WithdrawalsRootMismatch.to_string()),
+            ),
+        }
+    }
+
+    /// Root of the parent beacon block, to cross-check the block against the
+    /// beacon chain. `None` before Cancun
+    pub fn parent_beacon_block_root(&self) -> Option<B256> {
+        self.header.parent_beacon_block_root()
+    }
+
+    /// Base fee of the block, zero before London
+    fn base_fee(&self) -> u128 {
+        self.header
+            .base_fee_per_gas
+            .map_or(0, |base_fee| base_fee.saturating_to())
+    }
+
+    /// Fees paid by all the transactions of the block
+    pub fn total_fees(&self) -> Result<U256, ReceiptError> {
+        let base_fee = self.base_fee();
+
+        Ok(
+            std::iter::zip(self.transactions.iter(), self.per_transaction_gas()?)
+                .map(|(txn, gas_used)| U256::from(txn.effective_gas_price(base_fee)) * gas_used)
+                .sum(),
+        )
+    }
+
+    /// Lowest effective gas price paid by a transaction of the block, `None`
+    /// if the block is empty
+    pub fn min_gas_price(&self, base_fee: u128) -> Option<u128> {
+        self.transactions
+            .iter()
+            .map(|txn| txn.effective_gas_price(base_fee))
+            .min()
+    }
+
+    /// Highest effective gas price paid by a transaction of the block, `None`
+    /// if the block is empty
+    pub fn max_gas_price(&self, base_fee: u128) -> Option<u128> {
+        self.transactions
+            .iter()
+            .map(|txn| txn.effective_gas_price(base_fee))
+            .max()
+    }
+
+    /// Split `total_fees` between the burnt base fee (EIP-1559) and the tips
+    /// paid to the fee recipient
+    pub fn fee_breakdown(&self) -> Result<FeeBreakdown, ReceiptError> {
+        let burnt = U256::from(self.base_fee()) * self.header.gas_used;
+        let tips = self.total_fees()?.saturating_sub(burnt);
+
+        Ok(FeeBreakdown { burnt, tips })
+    }
+
+    /// Income of the fee recipient from the transactions: the fees above the
+    /// base fee. After the merge, it is the whole reward of the block, equal
+    /// to the tips of [Self::fee_breakdown].
+    ///
+    /// The static reward and the uncle rewards of blocks mined before the
+    /// merge are not included.
+    pub fn block_reward(&self) -> Result<U256, ReceiptError> {
+        let base_fee = self.base_fee();
+
+        Ok(
+            std::iter::zip(self.transactions.iter(), self.per_transaction_gas()?)
+                .map(|(txn, gas_used)| {
+                    let tip = txn.effective_gas_price(base_fee).saturating_sub(base_fee);
+                    U256::from(tip) * gas_used
+                })
+                .sum(),
+        )
+    }
+
+    /// Transactions, with their index, that emitted at least one log with
+    /// `topic0` as first topic, the signature of the event
+    pub fn transactions_emitting(&self, topic0: B256) -> Vec<(usize, &VerifiedTransaction)> {
+        self.transactions
+            .iter()
+            .enumerate()
+            .filter(|(_, txn)| {
+                txn.receipt()
+                    .logs
+                    .iter()
+                    .any(|log| log.topics().first() == Some(&topic0))
+            })
+            .collect()
+    }
+
+    /// Logs of the block in order, with their index in the block, the
+    /// `logIndex` of RPC responses
+    pub fn all_logs(&self) -> Vec<(u64, &Log)> {
+        self.transactions
+            .iter()
+            .flat_map(|txn| txn.receipt().logs.iter())
+            .enumerate()
+            .map(|(index, log)| (index as u64, log))
+            .collect()
+    }
+
+    /// Count the logs of the block and their topics
+    pub fn log_stats(&self) -> LogStats {
+        let logs = self
+            .transactions
+            .iter()
+            .flat_map(|txn| txn.receipt().logs.iter());
+
+        let (total_logs, total_topics) = logs.fold((0, 0), |(logs, topics), log| {
+            (logs + 1, topics + log.topics().len())
+        });
+
+        LogStats {
+            total_logs,
+            total_topics,
+            bloom_bits_set: self
+                .header
+                .logs_bloom
+                .iter()
+                .map(|byte| byte.count_ones())
+                .sum(),
+        }
+    }
+
+    /// Addresses called or created by the transactions of the block, and the
+    /// emitters of their logs
+    pub fn touched_addresses(&self) -> BTreeSet<Address> {
+        let targets = self
+            .transactions
+            .iter()
+            .filter_map(VerifiedTransaction::target_address);
+        let emitters = self
+            .transactions
+            .iter()
+            .flat_map(|txn| txn.receipt().logs.iter().map(|log| log.address));
+
+        targets.chain(emitters).collect()
     }
 
     /// Check if the block hash is correct
@@ -96,64 +961,567 @@ impl VerifiedBlock {
         &self.hash == hash
     }
 
-    /// Build transaction_trie
-    pub fn transaction_trie(&self) -> B256 {
-        let mut trie = HashBuilder::default();
-        let mut out: Vec<u8> = Vec::new();
-        let mut index_buffer: Vec<u8> = Vec::new();
+    /// Verify the block against its own header: the transactions, receipts
+    /// and withdrawals tries, the hash and, after the merge, the absence of
+    /// uncles
+    pub fn verify(&self) -> Result<(), VerifyError> {
+        self.verify_with_hash(self.hash)
+    }
 
-        let num_transactions = self.transactions.len();
+    /// Verify the block as a trusted checkpoint, a light client starts from
+    /// without its parent: it must hash to `trusted_hash`, and its tries must
+    /// match its header
+    pub fn verify_as_checkpoint(&self, trusted_hash: BlockHash) -> Result<(), VerifyError> {
+        self.verify_with_hash(trusted_hash)
+    }
+
+    fn verify_with_hash(&self, hash: BlockHash) -> Result<(), VerifyError> {
+        self.verify_against(
+            self.header.transaction_root,
+            self.header.receipts_root,
+            hash,
+        )?;
+
+        // Blocks have no difficulty after the merge
+        if self.header.difficulty.is_zero() && self.header.uncles_hash != EMPTY_UNCLES_HASH {
+            return Err(VerifyError::UnclesHashMismatch);
+        }
+
+        if !self.verify_withdrawals_root() {
+            return Err(VerifyError::WithdrawalsRootMismatch);
+        }
+
+        Ok(())
+    }
 
-        for index in 0..num_transactions {
-            out.clear();
-            index_buffer.clear();
+    /// Check that `uncles` are the ones committed to by the header
+    pub fn verify_uncles(&self, uncles: &[BlockHeader]) -> bool {
+        let uncles_hash = if uncles.is_empty() {
+            EMPTY_UNCLES_HASH
+        } else {
+            let mut buffer = Vec::<u8>::new();
+            alloy_rlp::encode_list(uncles, &mut buffer);
 
-            let index = index_for_rlp(index, num_transactions);
+            keccak256(buffer)
+        };
+
+        self.header.uncles_hash == uncles_hash
+    }
+
+    /// Verify the block against roots and hash obtained from a trusted
+    /// source, rather than against the fields of its own header.
+    ///
+    /// The tries are built from the transactions and receipts, the hash from
+    /// the header.
+    pub fn verify_against(
+        &self,
+        tx_root: B256,
+        receipts_root: B256,
+        block_hash: BlockHash,
+    ) -> Result<(), VerifyError> {
+        if self.transaction_trie() != tx_root {
+            return Err(VerifyError::TransactionRootMismatch);
+        }
 
-            self.transactions[index].encode(&mut out);
-            index.encode(&mut index_buffer);
+        if self.receipt_trie() != receipts_root {
+            return Err(VerifyError::ReceiptsRootMismatch);
+        }
 
-            trie.add_leaf(Nibbles::unpack(&index_buffer), &out);
+        if self.header.hash() != block_hash {
+            return Err(VerifyError::BlockHashMismatch);
         }
 
-        trie.root()
+        Ok(())
+    }
+
+    /// Build transaction_trie
+    pub fn transaction_trie(&self) -> B256 {
+        ordered_trie_root(&self.transactions)
+    }
+
+    /// Order in which the transactions are added to the transaction trie:
+    /// for each one, its index in the block and its RLP encoded trie key
+    pub fn trie_key_order(&self) -> Vec<(usize, Vec<u8>)> {
+        let num_transactions = self.transactions.len();
+
+        (0..num_transactions)
+            .map(|i| {
+                let index = index_for_rlp(i, num_transactions);
+                (index, alloy_rlp::encode(index))
+            })
+            .collect()
+    }
+
+    /// Build the proofs of the transactions at `indices`, in a single pass
+    /// over the transaction trie.
+    ///
+    /// Duplicated indices are proven once. Fails if an index is out of range.
+    pub fn transaction_proofs(
+        &self,
+        indices: &[usize],
+    ) -> Result<BTreeMap<usize, Vec<Bytes>>, RangeError> {
+        ordered_trie_proofs(&self.transactions, indices)
     }
 
     /// Build a receipt trie
     pub fn receipt_trie(&self) -> B256 {
-        let mut trie = HashBuilder::default();
-        let mut value_buffer: Vec<u8> = Vec::new();
-        let mut index_buffer: Vec<u8> = Vec::new();
+        let receipts: Vec<&VerifiedReceipt> =
+            self.transactions.iter().map(|txn| txn.receipt()).collect();
 
-        let num_transactions = self.transactions.len();
+        ordered_trie_root(&receipts)
+    }
 
-        for index in 0..num_transactions {
-            value_buffer.clear();
-            index_buffer.clear();
+    /// Build the proof of the receipt at `index` in the receipts trie
+    pub fn receipt_proof(&self, index: usize) -> Option<Vec<Bytes>> {
+        let receipts: Vec<&VerifiedReceipt> =
+            self.transactions.iter().map(|txn| txn.receipt()).collect();
+
+        ordered_trie_proofs(&receipts, &[index])
+            .ok()?
+            .remove(&index)
+    }
 
-            let index = index_for_rlp(index, num_transactions);
+    /// Build the proof of the log at `log_index` of the receipt at
+    /// `receipt_index`, `None` if there is no such receipt
+    pub fn message_proof(&self, receipt_index: usize, log_index: usize) -> Option<MessageProof> {
+        Some(MessageProof {
+            header: self.header.clone(),
+            receipt_index,
+            receipt: self.transactions.get(receipt_index)?.receipt().clone(),
+            receipt_proof: self.receipt_proof(receipt_index)?,
+            log_index,
+        })
+    }
+}
 
-            self.transactions[index].receipt().encode(&mut value_buffer);
-            index.encode(&mut index_buffer);
+/// Proof that a log, as a cross-chain message, was emitted in a block. It
+/// holds everything needed to check it against a trusted block hash
+#[derive(Debug, Clone)]
+pub struct MessageProof {
+    pub header: BlockHeader,
+    /// Index of the receipt in the block
+    pub receipt_index: usize,
+    pub receipt: VerifiedReceipt,
+    /// Proof of the receipt in the receipts trie
+    pub receipt_proof: Vec<Bytes>,
+    /// Index of the log in the receipt
+    pub log_index: usize,
+}
 
-            trie.add_leaf(Nibbles::unpack(&index_buffer), &value_buffer);
+impl MessageProof {
+    /// Check that the header hashes to `trusted_block_hash` and the receipt
+    /// is in its receipts trie, returning the proven log
+    pub fn verify(&self, trusted_block_hash: BlockHash) -> Result<&Log, ProofError> {
+        if self.header.hash() != trusted_block_hash {
+            return Err(ProofError::BlockHashMismatch);
         }
 
-        trie.root()
+        verify_receipt_in_block(
+            &self.header,
+            &self.receipt,
+            self.receipt_index,
+            &self.receipt_proof,
+        )?;
+
+        self.receipt
+            .logs
+            .get(self.log_index)
+            .ok_or(ProofError::LogNotFound {
+                log_index: self.log_index,
+            })
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use alloy_primitives::{address, fixed_bytes, uint};
+/// Check that `receipt` is the one at `index` in the block of `header`,
+/// using a proof of the receipts trie
+pub fn verify_receipt_in_block(
+    header: &BlockHeader,
+    receipt: &VerifiedReceipt,
+    index: usize,
+    proof: &[Bytes],
+) -> Result<(), ProofError> {
+    let key = Nibbles::unpack(alloy_rlp::encode(index));
 
-    use super::*;
+    // Typed receipts are prefixed by their type
+    let mut value = Vec::new();
+    receipt.encode(&mut value);
 
-    #[test]
-    fn should_block_hash_correct() {
-        let header= BlockHeader {
-            parent: "0x9e8dd74d00937fddbbf465cb828acbdb9af2514a6e9d633589f5e4a047dfec5b".parse().unwrap(),
-            uncles_hash: "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347".parse().unwrap(),
+    verify_proof(header.receipts_root, &key, &value, proof)
+}
+
+/// Check that a block returned by the RPC is consistent: its transactions
+/// must build its transactions root, and its header must hash to its hash
+pub fn verify_ethers_block(
+    block: &prelude::Block<ethers::types::Transaction>,
+) -> Result<(), VerifyError> {
+    let verified =
+        VerifiedBlock::new_without_receipts(block).map_err(VerifyError::InvalidTransaction)?;
+
+    // The header of `verified` holds the computed root
+    if verified.header.transaction_root != B256::new(block.transactions_root.0) {
+        return Err(VerifyError::TransactionRootMismatch);
+    }
+    if block.hash.map(|hash| BlockHash::new(hash.0)) != Some(verified.hash) {
+        return Err(VerifyError::BlockHashMismatch);
+    }
+
+    Ok(())
+}
+
+/// Check the hash of a header from its raw RLP, without decoding it
+pub fn verify_block_hash_from_rlp(header_rlp: &[u8], expected: BlockHash) -> bool {
+    keccak256(header_rlp) == expected
+}
+
+/// Logs of a block, as returned by [VerifiedBlock::log_stats]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogStats {
+    pub total_logs: usize,
+    pub total_topics: usize,
+    /// Bits set in the `logs_bloom` of the header
+    pub bloom_bits_set: u32,
+}
+
+/// Outcome of a check of [VerificationReport]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub passed: bool,
+    /// Why the check failed, `ok` if it passed
+    pub message: String,
+}
+
+/// Outcome of every check of a block, as returned by
+/// [VerifiedBlock::explain_verification]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationReport {
+    pub hash: Check,
+    pub transaction_root: Check,
+    pub receipts_root: Check,
+    /// Cumulative gas of the receipts and gas used of the block
+    pub gas: Check,
+    pub bloom: Check,
+    /// Absence of uncles after the merge
+    pub uncles: Check,
+    pub withdrawals: Check,
+}
+
+impl VerificationReport {
+    /// Whether every check passed
+    pub fn passed(&self) -> bool {
+        [
+            &self.hash,
+            &self.transaction_root,
+            &self.receipts_root,
+            &self.gas,
+            &self.bloom,
+            &self.uncles,
+            &self.withdrawals,
+        ]
+        .iter()
+        .all(|check| check.passed)
+    }
+}
+
+/// Data made available by a block, as returned by
+/// [VerifiedBlock::da_footprint]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaFootprint {
+    pub calldata_bytes: usize,
+    pub blob_count: usize,
+    pub blob_bytes: usize,
+}
+
+/// Fees of a block, as returned by [VerifiedBlock::fee_breakdown]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeBreakdown {
+    pub burnt: U256,
+    pub tips: U256,
+}
+
+/// Check that `headers` form a chain: each header is the child of the
+/// previous one.
+pub fn verify_header_chain(headers: &[BlockHeader]) -> Result<(), ChainError> {
+    for window in headers.windows(2) {
+        verify_link(&window[0], &window[1])?;
+    }
+
+    Ok(())
+}
+
+/// Check that `header` is the child of `parent`
+fn verify_link(parent: &BlockHeader, header: &BlockHeader) -> Result<(), ChainError> {
+    let number = header.number.to::<u64>();
+
+    if header.number != parent.number + U64::from(1) {
+        return Err(ChainError::NonSequentialNumber { number });
+    }
+
+    if header.parent != parent.hash() {
+        return Err(ChainError::ParentHashMismatch { number });
+    }
+
+    Ok(())
+}
+
+/// Check each block against its header, as [VerifiedBlock::verify], and that
+/// the blocks form a chain. Errors are reported for the first failing block.
+pub fn verify_blocks(blocks: &[VerifiedBlock]) -> Result<(), RangeError> {
+    for (index, block) in blocks.iter().enumerate() {
+        if let Some(parent) = index.checked_sub(1).map(|parent| &blocks[parent]) {
+            verify_link(&parent.header, &block.header)?;
+        }
+
+        block.verify().map_err(|error| RangeError::InvalidBlock {
+            number: block.header.number.to(),
+            error,
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Check that `headers` form a chain starting from the genesis block.
+///
+/// The genesis block has no parent, hence it can only be checked against
+/// its known hash.
+pub fn verify_from_genesis(
+    headers: &[BlockHeader],
+    genesis_hash: BlockHash,
+) -> Result<(), ChainError> {
+    let genesis = headers.first().ok_or(ChainError::Empty)?;
+
+    if genesis.number != U64::ZERO
+        || genesis.parent != BlockHash::ZERO
+        || genesis.hash() != genesis_hash
+    {
+        return Err(ChainError::InvalidGenesis);
+    }
+
+    verify_header_chain(headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256, fixed_bytes, uint, LogData, TxKind};
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+    use crate::{
+        receipt::tests::receipt_fixture,
+        transaction::tests::{
+            any_address, any_b256, any_bytes, any_u256, legacy_fixture, transfer_fixture,
+            type1_fixture, type2_fixture, type3_fixture,
+        },
+    };
+
+    /// Headers of any fork. Optional fields are only present after the
+    /// previous ones, as they are encoded without a placeholder.
+    fn any_header() -> impl Strategy<Value = BlockHeader> {
+        (
+            (
+                any_b256(),
+                any_b256(),
+                any_address(),
+                any_b256(),
+                any_b256(),
+            ),
+            (any_b256(), any::<[u8; 256]>(), any_u256(), any::<u64>()),
+            (any_u256(), any_u256(), any_u256(), any_bytes()),
+            (any_b256(), any::<[u8; 8]>()),
+            (
+                any_u256(),
+                any_b256(),
+                any::<(u64, u64)>(),
+                any_b256(),
+                any_b256(),
+            ),
+            0..=6usize,
+        )
+            .prop_map(
+                |(
+                    (parent, uncles_hash, miner, state_root, transaction_root),
+                    (receipts_root, logs_bloom, difficulty, number),
+                    (gas_limit, gas_used, timestamp, extra_data),
+                    (mix_hash, nonce),
+                    (
+                        base_fee,
+                        withdrawals_root,
+                        (blob_gas_used, excess_blob_gas),
+                        beacon,
+                        requests,
+                    ),
+                    forks,
+                )| BlockHeader {
+                    parent,
+                    uncles_hash,
+                    miner,
+                    state_root,
+                    transaction_root,
+                    receipts_root,
+                    logs_bloom: Bloom::from(logs_bloom),
+                    difficulty,
+                    number: U64::from(number),
+                    gas_limit,
+                    gas_used,
+                    timestamp,
+                    extra_data,
+                    mix_hash,
+                    nonce: B64::from(nonce),
+                    base_fee_per_gas: (forks > 0).then_some(base_fee),
+                    withdrawals_root: (forks > 1).then_some(withdrawals_root),
+                    blob_gas_used: (forks > 2).then_some(U64::from(blob_gas_used)),
+                    excess_blob_gas: (forks > 3).then_some(U64::from(excess_blob_gas)),
+                    parent_beacon_block_root: (forks > 4).then_some(beacon),
+                    requests_hash: (forks > 5).then_some(requests),
+                },
+            )
+    }
+
+    #[test]
+    fn should_decode_header_rlp() {
+        for header in [mainnet_genesis(), fixture_header(), cancun_header()] {
+            let buffer = alloy_rlp::encode(&header);
+
+            let decoded = BlockHeader::decode_rlp(&buffer).unwrap();
+            assert_eq!(decoded, header);
+            assert_eq!(decoded.hash(), header.hash());
+        }
+
+        let mut buffer = alloy_rlp::encode(fixture_header());
+        buffer.push(0x80);
+        assert_eq!(
+            BlockHeader::decode_rlp(&buffer),
+            Err(DecodeError::TrailingBytes(1))
+        );
+        let (header, rest) = BlockHeader::decode_rlp_lenient(&buffer).unwrap();
+        assert_eq!(header, fixture_header());
+        assert_eq!(rest, [0x80]);
+    }
+
+    proptest! {
+        #[test]
+        fn should_decode_what_is_encoded(header in any_header()) {
+            let buffer = alloy_rlp::encode(&header);
+
+            let decoded = BlockHeader::decode_rlp(&buffer).unwrap();
+            prop_assert_eq!(alloy_rlp::encode(&decoded), buffer);
+            prop_assert_eq!(decoded, header);
+        }
+
+        #[test]
+        fn should_decode_arbitrary_bytes_without_panicking(bytes in vec(any::<u8>(), 0..1024)) {
+            let _ = BlockHeader::decode_rlp(&bytes);
+        }
+    }
+
+    const MAINNET_GENESIS_HASH: BlockHash =
+        b256!("d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3");
+
+    fn mainnet_genesis() -> BlockHeader {
+        BlockHeader {
+            parent: BlockHash::ZERO,
+            uncles_hash: "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"
+                .parse()
+                .unwrap(),
+            miner: Address::ZERO,
+            state_root: "0xd7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544"
+                .parse()
+                .unwrap(),
+            transaction_root: "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+                .parse()
+                .unwrap(),
+            receipts_root: "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+                .parse()
+                .unwrap(),
+            logs_bloom: Bloom::ZERO,
+            difficulty: uint!(17179869184_U256),
+            number: U64::ZERO,
+            gas_limit: uint!(5000_U256),
+            gas_used: U256::ZERO,
+            timestamp: U256::ZERO,
+            extra_data: "0x11bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82fa"
+                .parse()
+                .unwrap(),
+            mix_hash: B256::ZERO,
+            nonce: "0x0000000000000042".parse().unwrap(),
+            base_fee_per_gas: None,
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            requests_hash: None,
+        }
+    }
+
+    fn mainnet_block_one() -> BlockHeader {
+        BlockHeader {
+            parent: MAINNET_GENESIS_HASH,
+            uncles_hash: "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"
+                .parse()
+                .unwrap(),
+            miner: address!("05a56E2D52c817161883f50c441c3228CFe54d9f"),
+            state_root: "0xd67e4d450343046425ae4271474353857ab860dbc0a1dde64b41b5cd3a532bf3"
+                .parse()
+                .unwrap(),
+            transaction_root: "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+                .parse()
+                .unwrap(),
+            receipts_root: "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+                .parse()
+                .unwrap(),
+            logs_bloom: Bloom::ZERO,
+            difficulty: uint!(17171480576_U256),
+            number: uint!(1_U64),
+            gas_limit: uint!(5000_U256),
+            gas_used: U256::ZERO,
+            timestamp: uint!(1438269988_U256),
+            extra_data: "0x476574682f76312e302e302f6c696e75782f676f312e342e32"
+                .parse()
+                .unwrap(),
+            mix_hash: "0x969b900de27b6ac6a67742365dd65f55a0526c41fd18e1b16f1a1215c2e66f59"
+                .parse()
+                .unwrap(),
+            nonce: "0x539bd4979fef1ec4".parse().unwrap(),
+            base_fee_per_gas: None,
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            requests_hash: None,
+        }
+    }
+
+    /// Header of mainnet block 19449567, after Cancun
+    fn cancun_header() -> BlockHeader {
+        BlockHeader {
+            parent: b256!("90926e0298d418181bd20c23b332451e35fd7d696b5dcdc5a3a0a6b715f4c717"),
+            uncles_hash: EMPTY_UNCLES_HASH,
+            miner: address!("95222290dd7278aa3ddd389cc1e1d165cc4bafe5"),
+            state_root: b256!("707875120a7103621fb4131df59904cda39de948dfda9084a1e3da44594d5404"),
+            transaction_root: b256!("889a1c26dc42ba829dab552b779620feac231cde8a6c79af022bdc605c23a780"),
+            receipts_root: b256!("d43aa19ecb03571d1b86d89d9bb980139d32f2f2ba59646cd5c1de9e80c68c90"),
+            logs_bloom: "0xc36919406572730518285284f2293101104140c0d42c4a786c892467868a8806f40159d29988002870403902413a1d04321320308da2e845438429e0012a00b419d8ccc8584a1c28f82a415d04eab8a5ae75c00d07761acf233414c08b6d9b571c06156086c70ea5186e9b989b0c2d55c0213c936805cd2ab331589c90194d070c00867549b1e1be14cb24500b0386cd901197c1ef5a00da453234fa48f3003dcaa894e3111c22b80e17f7d4388385a10720cda1140c0400f9e084ca34fc4870fb16b472340a2a6a63115a82522f506c06c2675080508834828c63defd06bc2331b4aa708906a06a560457b114248041e40179ebc05c6846c1e922125982f427".parse().unwrap(),
+            difficulty: U256::ZERO,
+            number: uint!(19449567_U64),
+            gas_limit: uint!(30000000_U256),
+            gas_used: uint!(11535164_U256),
+            timestamp: uint!(1710617795_U256),
+            extra_data: "0x6265617665726275696c642e6f7267".parse().unwrap(),
+            mix_hash: b256!("4c068e902990f21f92a2456fc75c59bec8be03b7f13682b6ebd27da56269beb5"),
+            nonce: B64::ZERO,
+            base_fee_per_gas: Some(uint!(36619559341_U256)),
+            withdrawals_root: Some(b256!("360c33f20eeed5efbc7d08be46e58f8440af5db503e40908ef3d1eb314856ef7")),
+            blob_gas_used: Some(U64::ZERO),
+            excess_blob_gas: Some(U64::ZERO),
+            parent_beacon_block_root: Some(b256!("2843cb9f7d001bd58816a915e685ed96a555c9aeec1217736bd83a96ebd409cc")),
+            requests_hash: None,
+        }
+    }
+
+    fn fixture_header() -> BlockHeader {
+        BlockHeader {
+            parent: "0x9e8dd74d00937fddbbf465cb828acbdb9af2514a6e9d633589f5e4a047dfec5b".parse().unwrap(),
+            uncles_hash: "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347".parse().unwrap(),
             miner: address!("95222290DD7278Aa3Ddd389Cc1E1d165CC4BAfe5"),
             state_root: "0xf7f5ceaac85a1ecd7e0c74f6af0cc2d2a88aca9ab9e356c12d1670322ec7fbdd".parse().unwrap(),
             transaction_root: "0x541e0fa363e67d568a0c99bf0b9c0f5cf6a268137072d33a4bca36d784542007".parse().unwrap(),
@@ -167,9 +1535,35 @@ mod tests {
             extra_data:"0x6265617665726275696c642e6f7267".parse().unwrap(),
             mix_hash:"0xf380df736ba8959509e0214cdf0862db0f45731d950789a2780a821faabc15a8".parse().unwrap(),
             nonce: "0x0000000000000000".parse().unwrap(),
-            base_fee_per_gas: uint!(41014545799_U256),
-            withdrawals_root: "0x89b1b0500a08b49ec6f538aedb39aab1c384874bff882edc4560e76c76ef3f05".parse().unwrap()
-        };
+            base_fee_per_gas: Some(uint!(41014545799_U256)),
+            withdrawals_root: Some("0x89b1b0500a08b49ec6f538aedb39aab1c384874bff882edc4560e76c76ef3f05".parse().unwrap()),
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            requests_hash: None,
+        }
+    }
+
+    /// Block of `num_transactions` type 2 transactions, each using as much
+    /// gas as the fixture, with its receipts root set
+    fn receipts_block(num_transactions: u64) -> VerifiedBlock {
+        let transactions = (0..num_transactions)
+            .map(|index| {
+                let mut txn = type2_fixture();
+                txn.receipt = receipt_fixture(Some(2));
+                txn.receipt.cumulative_gas_used = U256::from(46_109 * (index + 1));
+                VerifiedTransaction::Eip1559(txn)
+            })
+            .collect();
+
+        let mut block = VerifiedBlock::from_parts(fixture_header(), transactions);
+        block.header.receipts_root = block.receipt_trie();
+        block
+    }
+
+    #[test]
+    fn should_block_hash_correct() {
+        let header = fixture_header();
 
         let mut buffer = Vec::<u8>::new();
         header.encode(&mut buffer);
@@ -179,10 +1573,1450 @@ mod tests {
             hash,
             header,
             transactions: Vec::new(),
+            withdrawals: None,
+            transaction_hashes: OnceLock::new(),
         };
 
         assert!(block.verify_block_hash(&fixed_bytes!(
             "8c07fbc176e8cd1b0ea49dc56132e6e571d0c94ef0b88907658c7d197c4a9dfc"
         )))
     }
+
+    #[test]
+    fn should_order_trie_keys() {
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(legacy_fixture()),
+                VerifiedTransaction::Eip2930(type1_fixture()),
+                VerifiedTransaction::Eip1559(type2_fixture()),
+            ],
+        );
+
+        let order = block.trie_key_order();
+        let indices: Vec<usize> = order.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indices, vec![1, 2, 0]);
+
+        let keys: Vec<Nibbles> = order.iter().map(|(_, key)| Nibbles::unpack(key)).collect();
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn should_verify_block_hash_from_rlp() {
+        let expected = b256!("8c07fbc176e8cd1b0ea49dc56132e6e571d0c94ef0b88907658c7d197c4a9dfc");
+        let mut header_rlp = alloy_rlp::encode(fixture_header());
+
+        assert!(verify_block_hash_from_rlp(&header_rlp, expected));
+
+        header_rlp[1] ^= 1;
+        assert!(!verify_block_hash_from_rlp(&header_rlp, expected));
+    }
+
+    #[test]
+    fn should_verify_as_checkpoint() {
+        let checkpoint = b256!("88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6");
+        let block = VerifiedBlock::from_parts(mainnet_block_one(), Vec::new());
+        assert_eq!(block.verify_as_checkpoint(checkpoint), Ok(()));
+        assert_eq!(
+            block.verify_as_checkpoint(MAINNET_GENESIS_HASH),
+            Err(VerifyError::BlockHashMismatch)
+        );
+
+        // Withdrawals are committed to, but missing
+        let mut header = mainnet_block_one();
+        header.withdrawals_root = Some(alloy_trie::EMPTY_ROOT_HASH);
+        let block = VerifiedBlock::from_parts(header, Vec::new());
+        assert_eq!(
+            block.verify_as_checkpoint(block.header.hash()),
+            Err(VerifyError::WithdrawalsRootMismatch)
+        );
+    }
+
+    #[test]
+    fn should_explain_verification() {
+        let mut block = VerifiedBlock::from_parts(mainnet_block_one(), Vec::new());
+        assert!(block.explain_verification().passed());
+
+        // Both the hash and the gas used no longer match
+        block.header.gas_used = U256::from(21000);
+        let report = block.explain_verification();
+
+        assert!(!report.passed());
+        assert!(!report.hash.passed);
+        assert_eq!(report.hash.message, "block hash mismatch");
+        assert!(!report.gas.passed);
+        assert!(report.gas.message.contains("21000"));
+        for check in [
+            &report.transaction_root,
+            &report.receipts_root,
+            &report.bloom,
+            &report.uncles,
+            &report.withdrawals,
+        ] {
+            assert_eq!(check.message, "ok");
+        }
+
+        // A post-Shanghai header without the withdrawals of the block
+        let mut header = mainnet_block_one();
+        header.withdrawals_root = Some(alloy_trie::EMPTY_ROOT_HASH);
+        let block = VerifiedBlock::from_parts(header, Vec::new());
+        let report = block.explain_verification();
+
+        assert!(!report.passed());
+        assert!(!report.withdrawals.passed);
+        assert_eq!(report.withdrawals.message, "withdrawals root mismatch");
+        assert_eq!(block.verify(), Err(VerifyError::WithdrawalsRootMismatch));
+    }
+
+    #[test]
+    fn should_validate_uncles() {
+        let child = |parent: &BlockHeader, extra_data: &'static [u8]| {
+            let mut header = parent.clone();
+            header.parent = parent.hash();
+            header.number = parent.number + U64::from(1);
+            header.extra_data = Bytes::from_static(extra_data);
+            header
+        };
+        let mut chain = vec![mainnet_genesis()];
+        for _ in 0..10 {
+            chain.push(child(chain.last().unwrap(), b""));
+        }
+        let (including, ancestors) = chain.split_last().unwrap();
+        assert_eq!(including.number, U64::from(10));
+
+        // Sibling of the block 8, and of the block 3, 7 blocks behind
+        let uncle = child(&chain[7], b"uncle");
+        assert!(uncle.is_valid_uncle(including, ancestors));
+        let too_old = child(&chain[2], b"uncle");
+        assert!(!too_old.is_valid_uncle(including, ancestors));
+
+        // Canonical blocks are not uncles
+        assert!(!chain[8].is_valid_uncle(including, ancestors));
+        // Nor is a block whose parent is unknown
+        let mut orphan = child(&chain[7], b"uncle");
+        orphan.parent = B256::ZERO;
+        assert!(!orphan.is_valid_uncle(including, ancestors));
+    }
+
+    #[test]
+    fn should_verify_from_genesis() {
+        let headers = [mainnet_genesis(), mainnet_block_one()];
+
+        assert_eq!(
+            headers[1].hash(),
+            b256!("88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6")
+        );
+        assert_eq!(verify_from_genesis(&headers, MAINNET_GENESIS_HASH), Ok(()));
+        assert_eq!(
+            verify_from_genesis(&headers[1..], MAINNET_GENESIS_HASH),
+            Err(ChainError::InvalidGenesis)
+        );
+        assert_eq!(
+            verify_from_genesis(&[], MAINNET_GENESIS_HASH),
+            Err(ChainError::Empty)
+        );
+    }
+
+    #[test]
+    fn should_find_transaction_by_hash() {
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(legacy_fixture()),
+                VerifiedTransaction::Eip2930(type1_fixture()),
+                VerifiedTransaction::Eip1559(type2_fixture()),
+            ],
+        );
+
+        let hash = b256!("d6792b3f289f49876449d68af4706cc8cbfa5a9b480fdb7e5e0fa1fd79374348");
+        let (index, txn) = block.transaction_by_hash(&hash).unwrap();
+
+        assert_eq!(index, 2);
+        assert!(matches!(txn, VerifiedTransaction::Eip1559(_)));
+        assert_eq!(txn.hash(), hash);
+        assert!(block.transaction_by_hash(&B256::ZERO).is_none());
+        assert!(block.transaction_by_index(3).is_none());
+    }
+
+    #[test]
+    fn should_list_transaction_hashes() {
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(legacy_fixture()),
+                VerifiedTransaction::Eip2930(type1_fixture()),
+                VerifiedTransaction::Eip1559(type2_fixture()),
+            ],
+        );
+
+        assert_eq!(
+            block.transaction_hashes(),
+            vec![
+                b256!("2dd5d1a058f69df4c374081e0d6be639c65f8b39967d4ea8dc62ec77b4cca1d5"),
+                b256!("59ef7b47284897f1f68f4bd9612c2c388c4a7e5d0e6d9803bfb0d83cdee180c5"),
+                b256!("d6792b3f289f49876449d68af4706cc8cbfa5a9b480fdb7e5e0fa1fd79374348"),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_list_blobs_of_block() {
+        let first = type3_fixture().blob_versioned_hashes[0];
+        let (second, third) = (B256::repeat_byte(0x01), B256::repeat_byte(0x02));
+
+        let mut two_blobs = type3_fixture();
+        two_blobs.blob_versioned_hashes = vec![second, third];
+
+        let block = VerifiedBlock::from_parts(
+            cancun_header(),
+            vec![
+                VerifiedTransaction::Eip4844(type3_fixture()),
+                VerifiedTransaction::Eip1559(type2_fixture()),
+                VerifiedTransaction::Eip4844(two_blobs),
+            ],
+        );
+
+        assert_eq!(block.blob_count(), 3);
+        assert_eq!(block.blob_versioned_hashes(), vec![first, second, third]);
+
+        let block = VerifiedBlock::from_parts(cancun_header(), Vec::new());
+        assert_eq!(block.blob_count(), 0);
+        assert!(block.blob_versioned_hashes().is_empty());
+    }
+
+    #[test]
+    fn should_verify_receipt_type_consistency() {
+        let mut legacy = legacy_fixture();
+        legacy.receipt = receipt_fixture(Some(0));
+        let mut typed = type2_fixture();
+        typed.receipt = receipt_fixture(Some(2));
+        let mut block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(legacy),
+                VerifiedTransaction::Eip1559(typed),
+            ],
+        );
+        assert_eq!(block.verify_receipt_type_consistency(), Ok(()));
+
+        // The legacy receipt is a bare list, typed 0 or not
+        let mut encoded = Vec::<u8>::new();
+        block.transactions[0].receipt().encode(&mut encoded);
+        assert!(encoded[0] >= alloy_rlp::EMPTY_LIST_CODE);
+        assert_eq!(encoded, alloy_rlp::encode(receipt_fixture(None)));
+
+        let VerifiedTransaction::Eip1559(typed) = &mut block.transactions[1] else {
+            unreachable!()
+        };
+        typed.receipt.transaction_type = Some(1);
+        assert_eq!(
+            block.verify_receipt_type_consistency(),
+            Err(ReceiptError::TypeMismatch { index: 1 })
+        );
+    }
+
+    #[test]
+    fn should_compute_da_footprint() {
+        let mut calldata_heavy = type2_fixture();
+        calldata_heavy.data = vec![0xff; 4096].into();
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Eip1559(calldata_heavy),
+                VerifiedTransaction::Eip4844(type3_fixture()),
+            ],
+        );
+
+        let blob_count = type3_fixture().blob_versioned_hashes.len();
+        assert_eq!(
+            block.da_footprint(),
+            DaFootprint {
+                calldata_bytes: 4096 + type3_fixture().data.len(),
+                blob_count,
+                blob_bytes: blob_count * 131072,
+            }
+        );
+    }
+
+    #[test]
+    fn should_verify_blob_gas_used() {
+        let mut two_blobs = type3_fixture();
+        two_blobs
+            .blob_versioned_hashes
+            .push(two_blobs.blob_versioned_hashes[0]);
+
+        let mut header = fixture_header();
+        header.blob_gas_used = Some(uint!(393216_U64));
+        header.excess_blob_gas = Some(U64::ZERO);
+        header.parent_beacon_block_root = Some(B256::ZERO);
+
+        let mut block = VerifiedBlock::from_parts(
+            header,
+            vec![
+                VerifiedTransaction::Eip1559(type2_fixture()),
+                VerifiedTransaction::Eip4844(type3_fixture()),
+                VerifiedTransaction::Eip4844(two_blobs),
+            ],
+        );
+
+        assert_eq!(block.total_blob_gas(), 3 * GAS_PER_BLOB);
+        assert!(block.verify_blob_gas_used());
+
+        block.header.blob_gas_used = Some(uint!(131072_U64));
+        assert!(!block.verify_blob_gas_used());
+    }
+
+    #[test]
+    fn should_build_requested_transaction_proofs() {
+        let transactions = (0..6)
+            .map(|nonce| {
+                let mut txn = type2_fixture();
+                txn.nonce = nonce;
+                VerifiedTransaction::Eip1559(txn)
+            })
+            .collect();
+        let block = VerifiedBlock::from_parts(fixture_header(), transactions);
+        let root = block.transaction_trie();
+
+        assert_eq!(
+            block.transaction_proofs(&[5, 0, 6]),
+            Err(RangeError::IndexOutOfRange { index: 6, len: 6 })
+        );
+        let proofs = block.transaction_proofs(&[5, 0, 2, 2]).unwrap();
+        assert_eq!(proofs.keys().copied().collect::<Vec<_>>(), vec![0, 2, 5]);
+
+        for (index, proof) in &proofs {
+            let mut value = Vec::new();
+            block.transactions[*index].encode(&mut value);
+            let key = Nibbles::unpack(alloy_rlp::encode(index));
+
+            assert_eq!(verify_proof(root, &key, &value, proof), Ok(()));
+        }
+
+        let mut value = Vec::new();
+        block.transactions[2].encode(&mut value);
+        assert_eq!(
+            verify_proof(root, &Nibbles::unpack([0x80]), &value, &proofs[&0]),
+            Err(ProofError::ValueMismatch)
+        );
+    }
+
+    #[test]
+    fn should_verify_receipt_in_block() {
+        let block = receipts_block(4);
+
+        let proof = block.receipt_proof(2).unwrap();
+        let receipt = block.transactions[2].receipt();
+        assert_eq!(
+            verify_receipt_in_block(&block.header, receipt, 2, &proof),
+            Ok(())
+        );
+        assert_eq!(
+            verify_receipt_in_block(&block.header, block.transactions[1].receipt(), 2, &proof),
+            Err(ProofError::ValueMismatch)
+        );
+        assert!(block.receipt_proof(4).is_none());
+    }
+
+    #[test]
+    fn should_verify_message_proof() {
+        let transfer_topic =
+            b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+        let block = receipts_block(4);
+        let trusted_hash = block.header.hash();
+
+        let proof = block.message_proof(1, 0).unwrap();
+        let log = proof.verify(trusted_hash).unwrap();
+        assert_eq!(
+            log.address,
+            address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")
+        );
+        assert_eq!(log.topics()[0], transfer_topic);
+
+        assert_eq!(proof.verify(B256::ZERO), Err(ProofError::BlockHashMismatch));
+
+        let mut tampered = proof.clone();
+        tampered.receipt.logs[0].address = Address::ZERO;
+        assert_eq!(
+            tampered.verify(trusted_hash),
+            Err(ProofError::ValueMismatch)
+        );
+
+        let missing_log = block.message_proof(1, 1).unwrap();
+        assert_eq!(
+            missing_log.verify(trusted_hash),
+            Err(ProofError::LogNotFound { log_index: 1 })
+        );
+        assert!(block.message_proof(4, 0).is_none());
+    }
+
+    impl VerifiedBlock {
+        /// Transactions root built as the Yellow Paper defines the trie,
+        /// from the leaves sorted by key, independently of [index_for_rlp]
+        /// and [alloy_trie]
+        fn transaction_trie_reference(&self) -> B256 {
+            let leaves: BTreeMap<Vec<u8>, Vec<u8>> = self
+                .transactions
+                .iter()
+                .enumerate()
+                .map(|(index, txn)| {
+                    let key = alloy_rlp::encode(index);
+                    let nibbles = key.iter().flat_map(|byte| [byte >> 4, byte & 0x0f]);
+
+                    (nibbles.collect(), alloy_rlp::encode(txn))
+                })
+                .collect();
+            let leaves: Vec<(&[u8], &[u8])> = leaves
+                .iter()
+                .map(|(key, value)| (key.as_slice(), value.as_slice()))
+                .collect();
+
+            if leaves.is_empty() {
+                return keccak256([alloy_rlp::EMPTY_STRING_CODE]);
+            }
+            keccak256(reference_node(&leaves, 0))
+        }
+    }
+
+    /// Encode the node of the `leaves` sharing their first `depth` nibbles
+    fn reference_node(leaves: &[(&[u8], &[u8])], depth: usize) -> Vec<u8> {
+        let list = |items: &[Vec<u8>]| {
+            let mut out = Vec::new();
+            alloy_rlp::Header {
+                list: true,
+                payload_length: items.iter().map(Vec::len).sum(),
+            }
+            .encode(&mut out);
+            items.iter().for_each(|item| out.extend_from_slice(item));
+            out
+        };
+        // Nodes shorter than 32 bytes are embedded in their parent
+        let reference = |node: Vec<u8>| {
+            if node.len() < 32 {
+                node
+            } else {
+                alloy_rlp::encode(keccak256(node))
+            }
+        };
+
+        if let [(key, value)] = leaves {
+            return list(&[
+                alloy_rlp::encode(hex_prefix(&key[depth..], true).as_slice()),
+                alloy_rlp::encode(*value),
+            ]);
+        }
+
+        let first = leaves[0].0;
+        let shared = (depth..first.len())
+            .take_while(|i| {
+                leaves
+                    .iter()
+                    .all(|(key, _)| key.get(*i) == Some(&first[*i]))
+            })
+            .count();
+        if shared > 0 {
+            return list(&[
+                alloy_rlp::encode(hex_prefix(&first[depth..depth + shared], false).as_slice()),
+                reference(reference_node(leaves, depth + shared)),
+            ]);
+        }
+
+        let mut items: Vec<Vec<u8>> = (0..16)
+            .map(|nibble| {
+                let children: Vec<(&[u8], &[u8])> = leaves
+                    .iter()
+                    .filter(|(key, _)| key.get(depth) == Some(&nibble))
+                    .copied()
+                    .collect();
+
+                if children.is_empty() {
+                    vec![alloy_rlp::EMPTY_STRING_CODE]
+                } else {
+                    reference(reference_node(&children, depth + 1))
+                }
+            })
+            .collect();
+        let value = leaves.iter().find(|(key, _)| key.len() == depth);
+        items.push(
+            value.map_or(vec![alloy_rlp::EMPTY_STRING_CODE], |(_, value)| {
+                alloy_rlp::encode(*value)
+            }),
+        );
+
+        list(&items)
+    }
+
+    /// Hex-prefix encoding of a path of nibbles
+    fn hex_prefix(nibbles: &[u8], is_leaf: bool) -> Vec<u8> {
+        let flag = if is_leaf { 2 } else { 0 };
+        let (first, rest) = if nibbles.len() % 2 == 1 {
+            ((flag + 1) << 4 | nibbles[0], &nibbles[1..])
+        } else {
+            (flag << 4, nibbles)
+        };
+
+        std::iter::once(first)
+            .chain(rest.chunks(2).map(|pair| pair[0] << 4 | pair[1]))
+            .collect()
+    }
+
+    #[test]
+    fn should_match_reference_transaction_trie() {
+        let transaction_at = |index: usize| match index % 3 {
+            0 => {
+                let mut txn = legacy_fixture();
+                txn.nonce = index as u64;
+                VerifiedTransaction::Legacy(txn)
+            }
+            1 => VerifiedTransaction::Eip2930(type1_fixture()),
+            _ => VerifiedTransaction::Eip1559(type2_fixture()),
+        };
+
+        // Around the `0x7f` boundary of the key ordering
+        for num_transactions in [0, 1, 2, 3, 16, 127, 128, 129, 300] {
+            let block = VerifiedBlock::from_parts(
+                fixture_header(),
+                (0..num_transactions).map(transaction_at).collect(),
+            );
+
+            assert_eq!(
+                block.transaction_trie_reference(),
+                block.transaction_trie(),
+                "{num_transactions} transactions"
+            );
+        }
+    }
+
+    #[test]
+    fn should_single_transaction_trie_be_a_leaf() {
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![VerifiedTransaction::Eip1559(type2_fixture())],
+        );
+        assert_eq!(index_for_rlp(0, 1), 0);
+
+        let mut value = Vec::new();
+        block.transactions[0].encode(&mut value);
+
+        // The root is the leaf node itself, keyed by `rlp(0) = 0x80` whose
+        // hex-prefix encoding as an even leaf path is `0x2080`
+        let path = [0x20u8, 0x80];
+        let mut leaf = Vec::new();
+        alloy_rlp::Header {
+            list: true,
+            payload_length: path.as_slice().length() + value.as_slice().length(),
+        }
+        .encode(&mut leaf);
+        path.as_slice().encode(&mut leaf);
+        value.as_slice().encode(&mut leaf);
+
+        assert_eq!(block.transaction_trie(), keccak256(leaf));
+    }
+
+    #[test]
+    fn should_compute_total_fees() {
+        let mut legacy = legacy_fixture();
+        legacy.receipt.cumulative_gas_used = uint!(21000_U256);
+        let mut type2 = type2_fixture();
+        type2.receipt.cumulative_gas_used = uint!(71000_U256);
+
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(legacy),
+                VerifiedTransaction::Eip1559(type2),
+            ],
+        );
+
+        assert_eq!(
+            block.per_transaction_gas(),
+            Ok(vec![uint!(21000_U256), uint!(50000_U256)])
+        );
+
+        // The type 2 transaction pays no tip, only the base fee
+        let expected = uint!(300000000000_U256) * uint!(21000_U256)
+            + uint!(41014545799_U256) * uint!(50000_U256);
+        assert_eq!(block.total_fees(), Ok(expected));
+    }
+
+    #[test]
+    fn should_reject_decreasing_cumulative_gas() {
+        let mut first = legacy_fixture();
+        first.receipt.cumulative_gas_used = uint!(71000_U256);
+        let mut second = type2_fixture();
+        second.receipt.cumulative_gas_used = uint!(21000_U256);
+
+        // Receipts in reverse order
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(first),
+                VerifiedTransaction::Eip1559(second),
+            ],
+        );
+
+        let error = ReceiptError::CumulativeGasNotIncreasing { index: 1 };
+        assert_eq!(block.per_transaction_gas(), Err(error.clone()));
+        assert_eq!(block.total_fees(), Err(error.clone()));
+        assert_eq!(block.fee_breakdown(), Err(error));
+    }
+
+    #[test]
+    fn should_verify_receipts() {
+        let valid_block = || {
+            let mut block = receipts_block(2);
+            block.header.gas_used = uint!(92218_U256);
+            block.header.logs_bloom = block.transactions[0].receipt().logs_bloom;
+            block
+        };
+        assert_eq!(valid_block().verify_receipts(), Ok(()));
+
+        let mut block = valid_block();
+        block.header.receipts_root = B256::ZERO;
+        assert_eq!(
+            block.verify_receipts(),
+            Err(ReceiptError::ReceiptsRootMismatch)
+        );
+
+        let mut block = valid_block();
+        block.transactions.reverse();
+        block.header.receipts_root = block.receipt_trie();
+        assert_eq!(
+            block.verify_receipts(),
+            Err(ReceiptError::CumulativeGasNotIncreasing { index: 1 })
+        );
+
+        let mut block = valid_block();
+        block.header.gas_used = uint!(92219_U256);
+        assert_eq!(
+            block.verify_receipts(),
+            Err(ReceiptError::GasUsedMismatch {
+                header: uint!(92219_U256),
+                receipts: uint!(92218_U256)
+            })
+        );
+
+        let mut block = valid_block();
+        block.header.logs_bloom = Bloom::ZERO;
+        assert_eq!(
+            block.verify_receipts(),
+            Err(ReceiptError::LogsBloomMismatch)
+        );
+    }
+
+    #[test]
+    fn should_verify_cumulative_gas_monotonic() {
+        let transactions = |cumulative_gas: [u64; 3]| {
+            cumulative_gas
+                .into_iter()
+                .map(|gas| {
+                    let mut txn = legacy_fixture();
+                    txn.receipt.cumulative_gas_used = U256::from(gas);
+                    VerifiedTransaction::Legacy(txn)
+                })
+                .collect()
+        };
+
+        let block =
+            VerifiedBlock::from_parts(fixture_header(), transactions([21000, 71000, 92000]));
+        assert_eq!(block.verify_cumulative_gas_monotonic(), Ok(()));
+
+        let block =
+            VerifiedBlock::from_parts(fixture_header(), transactions([21000, 92000, 71000]));
+        assert_eq!(
+            block.verify_cumulative_gas_monotonic(),
+            Err(ReceiptError::CumulativeGasNotIncreasing { index: 2 })
+        );
+
+        let block = VerifiedBlock::from_parts(fixture_header(), transactions([0, 21000, 42000]));
+        assert_eq!(
+            block.verify_cumulative_gas_monotonic(),
+            Err(ReceiptError::CumulativeGasNotIncreasing { index: 0 })
+        );
+    }
+
+    #[test]
+    fn should_count_logs_of_block() {
+        let mut transfer = type2_fixture();
+        transfer.receipt = receipt_fixture(Some(2));
+        let mut no_logs = legacy_fixture();
+        no_logs.receipt.cumulative_gas_used = uint!(21000_U256);
+
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(no_logs),
+                VerifiedTransaction::Eip1559(transfer),
+            ],
+        );
+
+        assert_eq!(
+            block.log_stats(),
+            LogStats {
+                total_logs: 1,
+                total_topics: 3,
+                bloom_bits_set: 587,
+            }
+        );
+    }
+
+    #[test]
+    fn should_find_transactions_emitting_event() {
+        let transfer_topic =
+            b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+        let mut transfer = type2_fixture();
+        transfer.receipt = receipt_fixture(Some(2));
+        // The topic of the transfer, but not as first topic
+        let mut other_event = type2_fixture();
+        other_event.receipt = receipt_fixture(Some(2));
+        other_event.receipt.logs[0].data =
+            LogData::new_unchecked(vec![B256::ZERO, transfer_topic], Bytes::new());
+
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(legacy_fixture()),
+                VerifiedTransaction::Eip1559(other_event),
+                VerifiedTransaction::Eip1559(transfer),
+            ],
+        );
+
+        let emitting = block.transactions_emitting(transfer_topic);
+        assert_eq!(emitting.len(), 1);
+        assert_eq!(emitting[0].0, 2);
+        assert_eq!(emitting[0].1.hash(), block.transactions[2].hash());
+        assert_eq!(block.transactions_emitting(B256::ZERO)[0].0, 1);
+    }
+
+    #[test]
+    fn should_index_logs_across_block() {
+        let mut two_logs = type2_fixture();
+        two_logs.receipt = receipt_fixture(Some(2));
+        let mut second = two_logs.receipt.logs[0].clone();
+        second.address = Address::ZERO;
+        two_logs.receipt.logs.push(second);
+        let mut one_log = type2_fixture();
+        one_log.receipt = receipt_fixture(Some(2));
+
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Eip1559(two_logs),
+                VerifiedTransaction::Legacy(legacy_fixture()),
+                VerifiedTransaction::Eip1559(one_log),
+            ],
+        );
+
+        let logs = block.all_logs();
+        let indices: Vec<u64> = logs.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+        assert_eq!(logs[1].1.address, Address::ZERO);
+        assert_eq!(logs[2].1, &block.transactions[2].receipt().logs[0]);
+    }
+
+    #[test]
+    fn should_list_touched_addresses() {
+        let mut transfer = type2_fixture();
+        transfer.receipt = receipt_fixture(Some(2));
+        let mut create = legacy_fixture();
+        create.to = TxKind::Create;
+        let create = VerifiedTransaction::Legacy(create);
+        let created = create.created_address().unwrap();
+
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Eip1559(transfer),
+                create,
+                VerifiedTransaction::Eip1559(type2_fixture()),
+            ],
+        );
+
+        // The USDC contract emits the `Transfer` log
+        assert_eq!(
+            block.touched_addresses(),
+            BTreeSet::from([
+                address!("A69babEF1cA67A37Ffaf7a485DfFF3382056e78C"),
+                address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+                created,
+            ])
+        );
+    }
+
+    #[test]
+    fn should_size_encoded_body() {
+        let transactions = vec![
+            VerifiedTransaction::Legacy(legacy_fixture()),
+            VerifiedTransaction::Eip2930(type1_fixture()),
+            VerifiedTransaction::Eip1559(type2_fixture()),
+        ];
+        let mut block = VerifiedBlock::from_parts(fixture_header(), transactions);
+
+        let mut body_transactions = Vec::<u8>::new();
+        for txn in &block.transactions {
+            txn.encode_for_body(&mut body_transactions);
+        }
+        let encode_list = |items: &[&[u8]]| {
+            let payload = items.concat();
+            let mut out = Vec::<u8>::new();
+            alloy_rlp::Header {
+                list: true,
+                payload_length: payload.len(),
+            }
+            .encode(&mut out);
+            out.extend(payload);
+            out
+        };
+        let transactions = encode_list(&[&body_transactions]);
+        let uncles = encode_list(&[]);
+
+        let body = encode_list(&[&transactions, &uncles]);
+        assert_eq!(block.encoded_body_size(), body.len());
+
+        let withdrawals = vec![
+            Withdrawal {
+                index: 1,
+                validator_index: 2,
+                address: Address::ZERO,
+                amount: 3,
+            };
+            2
+        ];
+        let body = encode_list(&[&transactions, &uncles, &alloy_rlp::encode(&withdrawals)]);
+        block.withdrawals = Some(withdrawals);
+        assert_eq!(block.encoded_body_size(), body.len());
+    }
+
+    #[test]
+    fn should_hash_transactions_list() {
+        let transactions = vec![
+            VerifiedTransaction::Legacy(legacy_fixture()),
+            VerifiedTransaction::Eip1559(type2_fixture()),
+        ];
+        let block = VerifiedBlock::from_parts(fixture_header(), transactions);
+
+        let mut items = Vec::<u8>::new();
+        for txn in &block.transactions {
+            txn.encode_for_body(&mut items);
+        }
+        let mut list = Vec::<u8>::new();
+        alloy_rlp::Header {
+            list: true,
+            payload_length: items.len(),
+        }
+        .encode(&mut list);
+        list.extend(items);
+
+        let hash = block.transactions_list_hash();
+        assert_eq!(hash, keccak256(&list));
+        assert_eq!(hash, block.transactions_list_hash());
+        assert_ne!(hash, block.transaction_trie());
+    }
+
+    #[test]
+    fn should_verify_withdrawal_ordering() {
+        let withdrawal = |index| Withdrawal {
+            index,
+            validator_index: 1005753,
+            address: address!("b9d7934878b5fb9610b3fe8a5e441e8fad7e293f"),
+            amount: 18226960,
+        };
+        let mut block = VerifiedBlock::from_parts(fixture_header(), Vec::new());
+        assert!(block.verify_withdrawal_ordering());
+
+        block.withdrawals = Some(vec![withdrawal(38216111), withdrawal(38216112)]);
+        assert!(block.verify_withdrawal_ordering());
+
+        block.withdrawals = Some(vec![withdrawal(38216111), withdrawal(38216113)]);
+        assert!(!block.verify_withdrawal_ordering());
+    }
+
+    #[test]
+    fn should_find_gas_price_extremes() {
+        let base_fee = 41014545799;
+        let mut tipping = type2_fixture();
+        tipping.max_fee_per_gas = 50_000_000_000;
+        tipping.max_priority_fee_per_gas = 2_000_000_000;
+
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Eip1559(tipping),
+                VerifiedTransaction::Legacy(legacy_fixture()),
+                VerifiedTransaction::Eip1559(type2_fixture()),
+            ],
+        );
+
+        assert_eq!(block.min_gas_price(base_fee), Some(base_fee));
+        assert_eq!(block.max_gas_price(base_fee), Some(300_000_000_000));
+
+        // Once the base fee rises, the tipping transaction is capped by its max fee
+        assert_eq!(block.min_gas_price(60_000_000_000), Some(50_000_000_000));
+
+        let empty = VerifiedBlock::from_parts(fixture_header(), Vec::new());
+        assert_eq!(empty.min_gas_price(base_fee), None);
+        assert_eq!(empty.max_gas_price(base_fee), None);
+    }
+
+    #[test]
+    fn should_split_burnt_and_tip_fees() {
+        let mut legacy = legacy_fixture();
+        legacy.receipt.cumulative_gas_used = uint!(21000_U256);
+        let mut type2 = type2_fixture();
+        type2.receipt.cumulative_gas_used = uint!(71000_U256);
+
+        let mut header = fixture_header();
+        header.gas_used = uint!(71000_U256);
+        let block = VerifiedBlock::from_parts(
+            header,
+            vec![
+                VerifiedTransaction::Legacy(legacy),
+                VerifiedTransaction::Eip1559(type2),
+            ],
+        );
+
+        let fees = block.fee_breakdown().unwrap();
+        assert_eq!(Ok(fees.burnt + fees.tips), block.total_fees());
+        assert_eq!(
+            fees.burnt,
+            block.header.base_fee_per_gas.unwrap() * block.header.gas_used
+        );
+        // Only the legacy transaction tips
+        assert_eq!(
+            fees.tips,
+            (uint!(300000000000_U256) - uint!(41014545799_U256)) * uint!(21000_U256)
+        );
+    }
+
+    #[test]
+    fn should_compute_block_reward() {
+        let transactions = || {
+            let mut legacy = legacy_fixture();
+            legacy.receipt.cumulative_gas_used = uint!(21000_U256);
+            let mut type2 = type2_fixture();
+            type2.max_priority_fee_per_gas = 1_000_000_000;
+            type2.receipt.cumulative_gas_used = uint!(71000_U256);
+
+            vec![
+                VerifiedTransaction::Legacy(legacy),
+                VerifiedTransaction::Eip1559(type2),
+            ]
+        };
+
+        let mut header = fixture_header();
+        header.gas_used = uint!(71000_U256);
+        let block = VerifiedBlock::from_parts(header, transactions());
+        assert!(block.header.is_post_merge());
+        assert_eq!(
+            block.block_reward(),
+            Ok(block.fee_breakdown().unwrap().tips)
+        );
+        assert_eq!(
+            block.block_reward(),
+            Ok(
+                (uint!(300000000000_U256) - uint!(41014545799_U256)) * uint!(21000_U256)
+                    + uint!(1_000_000_000_U256) * uint!(50000_U256)
+            )
+        );
+
+        // Without base fee, all the fees go to the miner
+        let mut header = fixture_header();
+        header.base_fee_per_gas = None;
+        let block = VerifiedBlock::from_parts(header, transactions());
+        assert_eq!(block.block_reward(), block.total_fees());
+    }
+
+    #[test]
+    fn should_verify_against_trusted_roots() {
+        let mut legacy = legacy_fixture();
+        legacy.receipt = receipt_fixture(None);
+        let mut type2 = type2_fixture();
+        type2.receipt = receipt_fixture(Some(2));
+
+        let mut block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(legacy),
+                VerifiedTransaction::Eip1559(type2),
+            ],
+        );
+        let tx_root = block.transaction_trie();
+        let receipts_root = block.receipt_trie();
+
+        // The header can't be trusted for its roots
+        block.header.transaction_root = B256::ZERO;
+        block.header.receipts_root = B256::ZERO;
+        let block_hash = block.header.hash();
+
+        assert_eq!(
+            block.verify_against(tx_root, receipts_root, block_hash),
+            Ok(())
+        );
+        assert_eq!(
+            block.verify_against(B256::ZERO, receipts_root, block_hash),
+            Err(VerifyError::TransactionRootMismatch)
+        );
+        assert_eq!(
+            block.verify_against(tx_root, B256::ZERO, block_hash),
+            Err(VerifyError::ReceiptsRootMismatch)
+        );
+        assert_eq!(
+            block.verify_against(tx_root, receipts_root, fixture_header().hash()),
+            Err(VerifyError::BlockHashMismatch)
+        );
+    }
+
+    #[test]
+    fn should_empty_uncles_hash_be_hash_of_empty_list() {
+        assert_eq!(EMPTY_UNCLES_HASH, keccak256([alloy_rlp::EMPTY_LIST_CODE]));
+        assert_eq!(EMPTY_UNCLES_HASH, fixture_header().uncles_hash);
+    }
+
+    #[test]
+    fn should_verify_block_and_uncles() {
+        let mut legacy = legacy_fixture();
+        legacy.receipt = receipt_fixture(None);
+
+        let mut block =
+            VerifiedBlock::from_parts(fixture_header(), vec![VerifiedTransaction::Legacy(legacy)]);
+        block.header.transaction_root = block.transaction_trie();
+        block.header.receipts_root = block.receipt_trie();
+        block.header.withdrawals_root = Some(alloy_trie::EMPTY_ROOT_HASH);
+        block.withdrawals = Some(Vec::new());
+        block.hash = block.header.hash();
+
+        assert_eq!(block.verify(), Ok(()));
+        assert!(block.verify_uncles(&[]));
+        assert!(!block.verify_uncles(&[mainnet_genesis()]));
+
+        // A post-merge block can't have uncles
+        block.header.uncles_hash = B256::ZERO;
+        block.hash = block.header.hash();
+        assert_eq!(block.verify(), Err(VerifyError::UnclesHashMismatch));
+    }
+
+    #[test]
+    fn should_verify_blocks() {
+        // Empty blocks following block 18677559
+        let mut blocks: Vec<VerifiedBlock> = Vec::new();
+        for _ in 0..3 {
+            let mut header = fixture_header();
+            if let Some(parent) = blocks.last() {
+                header.parent = parent.header.hash();
+                header.number = parent.header.number + U64::from(1);
+            }
+            header.transaction_root = alloy_trie::EMPTY_ROOT_HASH;
+            header.receipts_root = alloy_trie::EMPTY_ROOT_HASH;
+            header.withdrawals_root = Some(alloy_trie::EMPTY_ROOT_HASH);
+
+            let mut block = VerifiedBlock::from_parts(header, Vec::new());
+            block.withdrawals = Some(Vec::new());
+            blocks.push(block);
+        }
+        assert_eq!(verify_blocks(&blocks), Ok(()));
+        assert_eq!(verify_blocks(&[]), Ok(()));
+
+        blocks[2].header.parent = B256::ZERO;
+        blocks[2].hash = blocks[2].header.hash();
+        assert_eq!(
+            verify_blocks(&blocks),
+            Err(RangeError::Chain(ChainError::ParentHashMismatch {
+                number: 18677561
+            }))
+        );
+
+        blocks[1].header.receipts_root = B256::ZERO;
+        assert_eq!(
+            verify_blocks(&blocks),
+            Err(RangeError::InvalidBlock {
+                number: 18677560,
+                error: VerifyError::ReceiptsRootMismatch
+            })
+        );
+
+        // The withdrawals root of the header commits to no withdrawal
+        blocks[0].withdrawals = Some(vec![Withdrawal {
+            index: 38633702,
+            validator_index: 570038,
+            address: address!("7cd1122e8e118b12ece8d25480dfeef230da17ff"),
+            amount: 18226960,
+        }]);
+        assert_eq!(
+            verify_blocks(&blocks),
+            Err(RangeError::InvalidBlock {
+                number: 18677559,
+                error: VerifyError::WithdrawalsRootMismatch
+            })
+        );
+    }
+
+    #[cfg(feature = "beacon")]
+    #[test]
+    fn should_compute_execution_payload_root() {
+        let transactions = || {
+            vec![
+                VerifiedTransaction::Eip1559(type2_fixture()),
+                VerifiedTransaction::Legacy(legacy_fixture()),
+            ]
+        };
+        let withdrawal = |index, validator_index, amount| Withdrawal {
+            index,
+            validator_index,
+            address: address!("b9d7934878b5fb9610b3fe8a5e441e8fad7e293f"),
+            amount,
+        };
+
+        // Expected roots generated by `scripts/execution_payload_roots.py`
+        let mut deneb = VerifiedBlock::from_parts(cancun_header(), transactions());
+        deneb.withdrawals = Some(vec![
+            withdrawal(38216111, 1005753, 18226960),
+            withdrawal(38216112, 1005754, 18300000),
+        ]);
+        assert_eq!(
+            deneb.execution_payload_root(),
+            Some(b256!(
+                "fb480d1de7e3dd76c4805fd7ff3916b7904266dd2fe6169ce5b3cde2a1166448"
+            ))
+        );
+
+        let mut capella = VerifiedBlock::from_parts(fixture_header(), transactions());
+        capella.withdrawals = Some(vec![withdrawal(26008521, 417862, 16772225)]);
+        assert_eq!(
+            capella.execution_payload_root(),
+            Some(b256!(
+                "2163d85cefef9371a70a73de26982f3d58b5422424fcb103b3b67e5167db9603"
+            ))
+        );
+
+        // The withdrawals are needed after Shanghai, and there is no payload
+        // before the merge
+        capella.withdrawals = None;
+        assert_eq!(capella.execution_payload_root(), None);
+        let pre_merge = VerifiedBlock::from_parts(mainnet_block_one(), Vec::new());
+        assert_eq!(pre_merge.execution_payload_root(), None);
+    }
+
+    #[test]
+    fn should_build_block_without_receipts() {
+        let transaction = ethers::types::Transaction {
+            transaction_type: Some(0.into()),
+            nonce: 1752.into(),
+            gas_price: Some(300000000000_u64.into()),
+            gas: 90277.into(),
+            to: Some(
+                "0x1643E812aE58766192Cf7D2Cf9567dF2C37e9B7F"
+                    .parse()
+                    .unwrap(),
+            ),
+            value: 3000000000000000000_u64.into(),
+            input: "0xa1903eab0000000000000000000000000000000000000000000000000000000000000000"
+                .parse()
+                .unwrap(),
+            v: 45.into(),
+            r: "0xb1df344bc5f8d4508b03bc24e73b8a411e6662152fc083bc044e59826cae3421"
+                .parse()
+                .unwrap(),
+            s: "0x08d15757b321670c81ad46e61eaa7c58279559af972d048648cfc40ba8ff4133"
+                .parse()
+                .unwrap(),
+            ..Default::default()
+        };
+        let block = prelude::Block {
+            author: Some(Default::default()),
+            logs_bloom: Some(Default::default()),
+            number: Some(18677559.into()),
+            mix_hash: Some(Default::default()),
+            nonce: Some(Default::default()),
+            transactions: vec![transaction],
+            ..Default::default()
+        };
+
+        let block = VerifiedBlock::new_without_receipts(&block).unwrap();
+        assert_eq!(
+            block.transaction_hashes(),
+            vec![b256!(
+                "2dd5d1a058f69df4c374081e0d6be639c65f8b39967d4ea8dc62ec77b4cca1d5"
+            )]
+        );
+        assert_eq!(
+            block.transaction_trie(),
+            ordered_trie_root(&[VerifiedTransaction::Legacy(legacy_fixture())])
+        );
+        assert_eq!(block.header.transaction_root, block.transaction_trie());
+        assert_eq!(
+            block.transactions[0].receipt().cumulative_gas_used,
+            U256::ZERO
+        );
+    }
+
+    #[test]
+    fn should_verify_ethers_block() {
+        // Block 3 of a development chain, from the tests of `ethers-core`
+        let json = r#"{"number":"0x3","hash":"0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972","parentHash":"0x689c70c080ca22bc0e681694fa803c1aba16a69c8b6368fed5311d279eb9de90","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","nonce":"0x0000000000000000","sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347","logsBloom":"0x00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000","transactionsRoot":"0x7270c1c4440180f2bd5215809ee3d545df042b67329499e1ab97eb759d31610d","stateRoot":"0x29f32984517a7d25607da485b23cefabfd443751422ca7e603395e1de9bc8a4b","receiptsRoot":"0x056b23fbba480696b65fe5a59b8f2148a1299103c4f57df839233af2cf4ca2d2","miner":"0x0000000000000000000000000000000000000000","difficulty":"0x0","totalDifficulty":"0x0","extraData":"0x","size":"0x3e8","gasLimit":"0x6691b7","gasUsed":"0x5208","timestamp":"0x5ecedbb9","transactions":[{"hash":"0xc3c5f700243de37ae986082fd2af88d2a7c2752a0c0f7b9d6ac47c729d45e067","nonce":"0x2","blockHash":"0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972","blockNumber":"0x3","transactionIndex":"0x0","from":"0xfdcedc3bfca10ecb0890337fbdd1977aba84807a","to":"0xdca8ce283150ab773bcbeb8d38289bdb5661de1e","value":"0x0","gas":"0x15f90","gasPrice":"0x4a817c800","input":"0x","v":"0x25","r":"0x19f2694eb9113656dbea0b925e2e7ceb43df83e601c4116aee9c0dd99130be88","s":"0x73e5764b324a4f7679d890a198ba658ba1c8cd36983ff9797e10b1b89dbb448e"}],"uncles":[]}"#;
+        let block: prelude::Block<ethers::types::Transaction> = serde_json::from_str(json).unwrap();
+        assert_eq!(verify_ethers_block(&block), Ok(()));
+
+        let mut wrong_root = block.clone();
+        wrong_root.transactions_root = Default::default();
+        assert_eq!(
+            verify_ethers_block(&wrong_root),
+            Err(VerifyError::TransactionRootMismatch)
+        );
+
+        let mut unknown_type = block.clone();
+        unknown_type.transactions[0].transaction_type = Some(0x42.into());
+        assert_eq!(
+            verify_ethers_block(&unknown_type),
+            Err(VerifyError::InvalidTransaction(
+                TransactionError::UnknownType { tx_type: 0x42 }
+            ))
+        );
+
+        let mut missing_gas_price = block.clone();
+        missing_gas_price.transactions[0].gas_price = None;
+        assert_eq!(
+            verify_ethers_block(&missing_gas_price),
+            Err(VerifyError::InvalidTransaction(
+                TransactionError::MissingField { field: "gasPrice" }
+            ))
+        );
+
+        // A pending block has no number
+        let mut pending = block.clone();
+        pending.number = None;
+        assert_eq!(
+            verify_ethers_block(&pending),
+            Err(VerifyError::InvalidTransaction(
+                TransactionError::MissingField { field: "number" }
+            ))
+        );
+
+        let mut wrong_hash = block;
+        wrong_hash.hash = Some(Default::default());
+        assert_eq!(
+            verify_ethers_block(&wrong_hash),
+            Err(VerifyError::BlockHashMismatch)
+        );
+    }
+
+    #[test]
+    fn should_detect_fork_from_fields() {
+        assert_eq!(mainnet_block_one().fork_from_fields(), Fork::PreLondon);
+
+        let mut header = fixture_header();
+        assert_eq!(header.fork_from_fields(), Fork::Shanghai);
+
+        header.parent_beacon_block_root = Some(B256::ZERO);
+        header.blob_gas_used = Some(U64::ZERO);
+        header.excess_blob_gas = Some(U64::ZERO);
+        assert_eq!(header.fork_from_fields(), Fork::Cancun);
+
+        header.requests_hash = Some(B256::ZERO);
+        assert_eq!(header.fork_from_fields(), Fork::Prague);
+
+        let mut header = fixture_header();
+        header.withdrawals_root = None;
+        assert_eq!(header.fork_from_fields(), Fork::Paris);
+
+        header.difficulty = uint!(1_U256);
+        assert_eq!(header.fork_from_fields(), Fork::London);
+    }
+
+    /// Genesis of Sepolia, which starts at London
+    fn sepolia_genesis() -> BlockHeader {
+        BlockHeader {
+            parent: B256::ZERO,
+            uncles_hash: EMPTY_UNCLES_HASH,
+            miner: Address::ZERO,
+            state_root: b256!("5eb6e371a698b8d68f665192350ffcecbbbf322916f4b51bd79bb6887da3f494"),
+            transaction_root: alloy_trie::EMPTY_ROOT_HASH,
+            receipts_root: alloy_trie::EMPTY_ROOT_HASH,
+            logs_bloom: Bloom::ZERO,
+            difficulty: uint!(0x20000_U256),
+            number: U64::ZERO,
+            gas_limit: uint!(30000000_U256),
+            gas_used: U256::ZERO,
+            timestamp: uint!(1633267481_U256),
+            extra_data: Bytes::from_static(b"Sepolia, Athens, Attica, Greece!"),
+            mix_hash: B256::ZERO,
+            nonce: B64::ZERO,
+            base_fee_per_gas: Some(uint!(1000000000_U256)),
+            withdrawals_root: None,
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            parent_beacon_block_root: None,
+            requests_hash: None,
+        }
+    }
+
+    #[test]
+    fn should_encode_for_fork_of_schedule() {
+        let sepolia = ForkSchedule {
+            blocks: BTreeMap::from([(Fork::London, 0)]),
+            ..Default::default()
+        };
+        let header = sepolia_genesis();
+        assert_eq!(header.fork_from_schedule(&sepolia), Fork::London);
+
+        let mut buffer = Vec::<u8>::new();
+        header.encode_for_fork(header.fork_from_schedule(&sepolia), &mut buffer);
+        assert_eq!(
+            keccak256(&buffer),
+            b256!("25a5cc106eea7138acab33231d7160d69cb777ee0c2c553fcddf5138993e6dd9")
+        );
+
+        // Mainnet heights would drop the base fee of the genesis
+        let mainnet = ForkSchedule::mainnet();
+        assert_eq!(header.fork_from_schedule(&mainnet), Fork::PreLondon);
+        let mut buffer = Vec::<u8>::new();
+        header.encode_for_fork(header.fork_from_schedule(&mainnet), &mut buffer);
+        assert_ne!(keccak256(&buffer), header.hash());
+
+        assert_eq!(
+            fixture_header().fork_from_schedule(&mainnet),
+            Fork::Shanghai
+        );
+        assert_eq!(cancun_header().fork_from_schedule(&mainnet), Fork::Cancun);
+        assert_eq!(Fork::from_block_number(15_537_393, &mainnet), Fork::London);
+        assert_eq!(Fork::from_block_number(15_537_394, &mainnet), Fork::Paris);
+    }
+
+    #[test]
+    fn should_find_fork_from_timestamp() {
+        let mainnet = ForkSchedule::mainnet();
+        let cancun = 1_710_338_135;
+
+        // First block of Cancun
+        let mut header = fixture_header();
+        header.number = uint!(19426587_U64);
+        header.timestamp = U256::from(cancun);
+        assert_eq!(header.fork_from_schedule(&mainnet), Fork::Cancun);
+
+        header.timestamp = U256::from(cancun - 12);
+        assert_eq!(header.fork_from_schedule(&mainnet), Fork::Shanghai);
+
+        assert_eq!(Fork::from_timestamp(cancun + 1, &mainnet), Fork::Cancun);
+        assert_eq!(Fork::from_timestamp(1_681_338_454, &mainnet), Fork::Paris);
+        assert_eq!(Fork::from_timestamp(u64::MAX, &mainnet), Fork::Prague);
+
+        // Timestamps don't matter before the merge
+        assert_eq!(
+            mainnet_block_one().fork_from_schedule(&mainnet),
+            Fork::PreLondon
+        );
+    }
+
+    #[test]
+    fn should_compute_gas_utilization() {
+        let utilization = fixture_header().gas_utilization();
+        assert!((utilization - 11754067.0 / 30000000.0).abs() < f64::EPSILON);
+        assert!((0.39..0.40).contains(&utilization));
+
+        let mut header = fixture_header();
+        header.gas_limit = U256::ZERO;
+        assert_eq!(header.gas_utilization(), 0.0);
+    }
+
+    #[test]
+    fn should_read_parent_beacon_block_root() {
+        let root = b256!("2843cb9f7d001bd58816a915e685ed96a555c9aeec1217736bd83a96ebd409cc");
+        assert_eq!(cancun_header().parent_beacon_block_root(), Some(root));
+
+        let block = VerifiedBlock::from_parts(cancun_header(), Vec::new());
+        assert_eq!(block.parent_beacon_block_root(), Some(root));
+
+        let shanghai = VerifiedBlock::from_parts(fixture_header(), Vec::new());
+        assert_eq!(shanghai.parent_beacon_block_root(), None);
+    }
+
+    #[test]
+    fn should_tell_post_merge_blocks() {
+        assert!(fixture_header().is_post_merge());
+        assert!(cancun_header().is_post_merge());
+        assert!(!mainnet_block_one().is_post_merge());
+
+        let mut header = fixture_header();
+        header.difficulty = uint!(58750003716598352816469_U256);
+        assert!(!header.is_post_merge());
+    }
+
+    #[test]
+    fn should_sum_value_transferred() {
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(legacy_fixture()),
+                VerifiedTransaction::Legacy(transfer_fixture()),
+                VerifiedTransaction::Eip1559(type2_fixture()),
+            ],
+        );
+        assert_eq!(
+            block.total_value_transferred(),
+            uint!(3000000000000000000_U256) + uint!(31337_U256) + uint!(11846912_U256)
+        );
+
+        let mut whale = legacy_fixture();
+        whale.value = U256::MAX;
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(whale),
+                VerifiedTransaction::Legacy(legacy_fixture()),
+            ],
+        );
+        assert_eq!(block.total_value_transferred(), U256::MAX);
+    }
+
+    #[test]
+    fn should_verify_withdrawals() {
+        let header = cancun_header();
+        assert_eq!(
+            header.hash(),
+            b256!("85cdcbe36217fd57bf2c33731d8460657a7ce512401f49c9f6392c82a7ccf7ac")
+        );
+
+        let mut block = VerifiedBlock::from_parts(header, Vec::new());
+        assert!(!block.verify_withdrawals_root());
+
+        // First withdrawal of the block, out of 16
+        let withdrawal = Withdrawal {
+            index: 38633702,
+            validator_index: 570038,
+            address: address!("7cd1122e8e118b12ece8d25480dfeef230da17ff"),
+            amount: 18226960,
+        };
+        block.withdrawals = Some(vec![withdrawal]);
+        assert_eq!(block.total_withdrawn(), 18226960);
+        // The root commits to all of them
+        assert!(!block.verify_withdrawals_root());
+
+        block.withdrawals = Some(Vec::new());
+        block.header.withdrawals_root = Some(alloy_trie::EMPTY_ROOT_HASH);
+        assert!(block.verify_withdrawals_root());
+        assert_eq!(block.total_withdrawn(), 0);
+
+        // Neither list nor root before Shanghai
+        let block = VerifiedBlock::from_parts(mainnet_block_one(), Vec::new());
+        assert!(block.verify_withdrawals_root());
+    }
+
+    #[test]
+    fn should_find_index_of_transaction() {
+        let block = VerifiedBlock::from_parts(
+            fixture_header(),
+            vec![
+                VerifiedTransaction::Legacy(legacy_fixture()),
+                VerifiedTransaction::Eip1559(type2_fixture()),
+            ],
+        );
+
+        let txn = VerifiedTransaction::Legacy(legacy_fixture());
+        assert_eq!(block.index_of_transaction(&txn), Some(0));
+
+        let txn = VerifiedTransaction::Eip1559(type2_fixture());
+        assert_eq!(block.index_of_transaction(&txn), Some(1));
+
+        let txn = VerifiedTransaction::Eip2930(type1_fixture());
+        assert_eq!(block.index_of_transaction(&txn), None);
+    }
 }
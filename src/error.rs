@@ -0,0 +1,324 @@
+//! Errors returned while verifying Ethereum data
+use std::fmt;
+
+use alloy_primitives::U256;
+
+/// Error returned when a sequence of headers does not form a chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainError {
+    /// No header was given
+    Empty,
+    /// The first header is not the expected genesis block
+    InvalidGenesis,
+    /// `parent` of the header does not match the hash of the previous one
+    ParentHashMismatch { number: u64 },
+    /// The header number does not follow the previous one
+    NonSequentialNumber { number: u64 },
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "empty header chain"),
+            Self::InvalidGenesis => write!(f, "first header is not the genesis block"),
+            Self::ParentHashMismatch { number } => {
+                write!(f, "parent hash mismatch at block {number}")
+            }
+            Self::NonSequentialNumber { number } => {
+                write!(f, "non sequential block number {number}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+/// Error returned when decoding RLP encoded data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// An integer is encoded with leading zero bytes
+    NonMinimalInteger,
+    /// The transaction type byte is not supported
+    UnknownTransactionType(u8),
+    /// Malformed RLP
+    Rlp(alloy_rlp::Error),
+    /// The input has this many bytes after the decoded item
+    TrailingBytes(usize),
+}
+
+impl From<alloy_rlp::Error> for DecodeError {
+    fn from(value: alloy_rlp::Error) -> Self {
+        match value {
+            alloy_rlp::Error::LeadingZero => Self::NonMinimalInteger,
+            error => Self::Rlp(error),
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonMinimalInteger => write!(f, "integer encoded with leading zeros"),
+            Self::UnknownTransactionType(tx_type) => {
+                write!(f, "unknown transaction type {tx_type}")
+            }
+            Self::Rlp(error) => write!(f, "malformed rlp: {error}"),
+            Self::TrailingBytes(count) => write!(f, "{count} trailing bytes after the item"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Error returned when a Merkle Patricia Trie proof is not valid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofError {
+    /// A node of the proof is not a valid trie node
+    InvalidNode,
+    /// The node at `depth` does not match the reference of its parent, or
+    /// the root for `depth` 0
+    HashMismatch { depth: usize },
+    /// The proof ends before reaching the key
+    IncompleteProof,
+    /// The proof shows that the key is not in the trie
+    KeyNotFound,
+    /// The key is in the trie with a different value
+    ValueMismatch,
+    /// A proof response misses a field or has an invalid one
+    InvalidResponse,
+    /// The header does not hash to the trusted block hash
+    BlockHashMismatch,
+    /// The proven receipt has no log at `log_index`
+    LogNotFound { log_index: usize },
+    /// The proof walks through more than `max_depth` nodes
+    TooDeep { max_depth: usize },
+}
+
+impl fmt::Display for ProofError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidNode => write!(f, "invalid trie node"),
+            Self::HashMismatch { depth } => write!(f, "node hash mismatch at depth {depth}"),
+            Self::IncompleteProof => write!(f, "proof ends before reaching the key"),
+            Self::KeyNotFound => write!(f, "key not in the trie"),
+            Self::ValueMismatch => write!(f, "key has a different value"),
+            Self::InvalidResponse => write!(f, "invalid proof response"),
+            Self::BlockHashMismatch => write!(f, "block hash mismatch"),
+            Self::LogNotFound { log_index } => write!(f, "receipt has no log {log_index}"),
+            Self::TooDeep { max_depth } => write!(f, "proof deeper than {max_depth} nodes"),
+        }
+    }
+}
+
+impl std::error::Error for ProofError {}
+
+/// Error returned when a receipt is not valid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReceiptError {
+    /// The log at `log_index` has more than 4 topics
+    TooManyTopics { log_index: usize, count: usize },
+    /// The cumulative gas used of the receipt at `index` is not greater than
+    /// the previous one
+    CumulativeGasNotIncreasing { index: usize },
+    /// The receipts trie root differs from the one of the header
+    ReceiptsRootMismatch,
+    /// The cumulative gas used of the last receipt differs from the gas used
+    /// of the header
+    GasUsedMismatch { header: U256, receipts: U256 },
+    /// The union of the blooms of the receipts differs from the logs bloom of
+    /// the header
+    LogsBloomMismatch,
+    /// The type of the receipt at `index` is not the one of its transaction
+    TypeMismatch { index: usize },
+    /// The type of the receipt does not fit in a byte
+    TypeOutOfRange { tx_type: u64 },
+}
+
+impl fmt::Display for ReceiptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyTopics { log_index, count } => {
+                write!(f, "log {log_index} has {count} topics, at most 4 allowed")
+            }
+            Self::CumulativeGasNotIncreasing { index } => {
+                write!(
+                    f,
+                    "cumulative gas used of receipt {index} does not increase"
+                )
+            }
+            Self::ReceiptsRootMismatch => write!(f, "receipts root mismatch"),
+            Self::GasUsedMismatch { header, receipts } => {
+                write!(f, "header uses {header} gas, receipts {receipts}")
+            }
+            Self::LogsBloomMismatch => write!(f, "logs bloom mismatch"),
+            Self::TypeMismatch { index } => {
+                write!(f, "receipt {index} is not typed as its transaction")
+            }
+            Self::TypeOutOfRange { tx_type } => {
+                write!(f, "receipt type {tx_type:#x} does not fit in a byte")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReceiptError {}
+
+/// Error returned when a transaction can't be represented
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionError {
+    /// The fee `field` does not fit in 128 bits
+    FeeOverflow { field: &'static str },
+    /// The priority fee of a fee market transaction is above its max fee
+    InvalidFeeMarket {
+        max_fee_per_gas: u128,
+        max_priority_fee_per_gas: u128,
+    },
+    /// The receipt of the transaction can't be converted
+    Receipt(ReceiptError),
+    /// The type of the transaction is not supported
+    UnknownType { tx_type: u64 },
+    /// The RPC field `field` is missing
+    MissingField { field: &'static str },
+    /// The RPC field `field` can't be parsed
+    InvalidField { field: &'static str },
+}
+
+impl From<ReceiptError> for TransactionError {
+    fn from(value: ReceiptError) -> Self {
+        Self::Receipt(value)
+    }
+}
+
+impl fmt::Display for TransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FeeOverflow { field } => write!(f, "{field} does not fit in 128 bits"),
+            Self::InvalidFeeMarket {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            } => write!(
+                f,
+                "max priority fee per gas {max_priority_fee_per_gas} above max fee per gas \
+                 {max_fee_per_gas}"
+            ),
+            Self::Receipt(error) => write!(f, "invalid receipt: {error}"),
+            Self::UnknownType { tx_type } => write!(f, "unknown transaction type {tx_type}"),
+            Self::MissingField { field } => write!(f, "missing field {field}"),
+            Self::InvalidField { field } => write!(f, "invalid field {field}"),
+        }
+    }
+}
+
+impl std::error::Error for TransactionError {}
+
+/// Error returned when the content of a block does not match trusted values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    /// The transactions trie root differs
+    TransactionRootMismatch,
+    /// The receipts trie root differs
+    ReceiptsRootMismatch,
+    /// The header hash differs
+    BlockHashMismatch,
+    /// A block after the merge has uncles
+    UnclesHashMismatch,
+    /// The withdrawals trie root differs
+    WithdrawalsRootMismatch,
+    /// A transaction of the block can't be represented
+    InvalidTransaction(TransactionError),
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TransactionRootMismatch => write!(f, "transactions root mismatch"),
+            Self::ReceiptsRootMismatch => write!(f, "receipts root mismatch"),
+            Self::BlockHashMismatch => write!(f, "block hash mismatch"),
+            Self::UnclesHashMismatch => write!(f, "uncles hash mismatch"),
+            Self::WithdrawalsRootMismatch => write!(f, "withdrawals root mismatch"),
+            Self::InvalidTransaction(error) => write!(f, "invalid transaction: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Error returned when a range of blocks, or an index in one, is not valid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RangeError {
+    /// The content of block `number` does not match its header
+    InvalidBlock { number: u64, error: VerifyError },
+    /// The blocks do not form a chain
+    Chain(ChainError),
+    /// `index` is out of a list of `len` items
+    IndexOutOfRange { index: usize, len: usize },
+}
+
+impl From<ChainError> for RangeError {
+    fn from(value: ChainError) -> Self {
+        Self::Chain(value)
+    }
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidBlock { number, error } => write!(f, "invalid block {number}: {error}"),
+            Self::Chain(error) => write!(f, "{error}"),
+            Self::IndexOutOfRange { index, len } => {
+                write!(f, "index {index} out of {len} items")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RangeError {}
+
+/// Error returned when a transaction breaks an invariant of its type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    /// A typed transaction has chain id 0
+    MissingChainId,
+    /// A blob transaction carries no blob
+    NoBlobHashes,
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingChainId => write!(f, "typed transaction without chain id"),
+            Self::NoBlobHashes => write!(f, "blob transaction without blob hashes"),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {}
+
+/// Error returned when an RPC response can't be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The response is not valid JSON or misses fields, with the message of
+    /// the parser
+    Json(String),
+    /// The receipt at `index` can't be converted
+    Receipt { index: usize, error: ReceiptError },
+    /// An address is not 20 bytes long
+    InvalidAddressLength { length: usize },
+    /// An amount of wei or ether is not a valid number
+    InvalidAmount(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(message) => write!(f, "invalid JSON: {message}"),
+            Self::Receipt { index, error } => write!(f, "invalid receipt {index}: {error}"),
+            Self::InvalidAddressLength { length } => {
+                write!(f, "address of {length} bytes, expected 20")
+            }
+            Self::InvalidAmount(amount) => write!(f, "invalid amount {amount}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
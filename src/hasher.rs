@@ -0,0 +1,87 @@
+//! Keccak256 used to hash blocks, transactions and proof nodes.
+//!
+//! With the `custom-keccak` feature the crate hashes through
+//! `proof_eth_custom_keccak256`, a function the final binary must define:
+//!
+//! ```ignore
+//! #[no_mangle]
+//! fn proof_eth_custom_keccak256(data: &[u8]) -> alloy_primitives::B256 {
+//!     // e.g. call the precompile of a zkVM
+//! }
+//! ```
+//!
+//! Tries are then built by [crate::trie::HasherTrie], as `alloy_trie` always
+//! hashes with `alloy_primitives`.
+use alloy_primitives::B256;
+
+/// A keccak256 implementation
+pub trait Hasher {
+    fn keccak256(data: &[u8]) -> B256;
+}
+
+/// Keccak256 from `alloy_primitives`
+#[derive(Debug, Default)]
+pub struct AlloyHasher;
+
+impl Hasher for AlloyHasher {
+    fn keccak256(data: &[u8]) -> B256 {
+        alloy_primitives::keccak256(data)
+    }
+}
+
+/// Keccak256 provided by the final binary
+#[cfg(feature = "custom-keccak")]
+#[derive(Debug, Default)]
+pub struct CustomHasher;
+
+#[cfg(feature = "custom-keccak")]
+extern "Rust" {
+    fn proof_eth_custom_keccak256(data: &[u8]) -> B256;
+}
+
+#[cfg(feature = "custom-keccak")]
+impl Hasher for CustomHasher {
+    fn keccak256(data: &[u8]) -> B256 {
+        // SAFETY: the symbol is defined by the binary enabling the feature
+        unsafe { proof_eth_custom_keccak256(data) }
+    }
+}
+
+/// Hasher used by the crate
+#[cfg(not(feature = "custom-keccak"))]
+pub type DefaultHasher = AlloyHasher;
+
+/// Hasher used by the crate
+#[cfg(feature = "custom-keccak")]
+pub type DefaultHasher = CustomHasher;
+
+/// Hash `data` with the [DefaultHasher]
+pub fn keccak256<T: AsRef<[u8]>>(data: T) -> B256 {
+    DefaultHasher::keccak256(data.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::b256;
+
+    use super::*;
+
+    #[cfg(feature = "custom-keccak")]
+    #[no_mangle]
+    fn proof_eth_custom_keccak256(data: &[u8]) -> B256 {
+        alloy_primitives::keccak256(data)
+    }
+
+    #[test]
+    fn should_default_hasher_hash_correctly() {
+        assert_eq!(
+            keccak256([]),
+            b256!("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470")
+        );
+        // Hash of the empty list, the uncles hash of post-merge blocks
+        assert_eq!(
+            keccak256([alloy_rlp::EMPTY_LIST_CODE]),
+            b256!("1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347")
+        );
+    }
+}
@@ -0,0 +1,591 @@
+//! Read and verify RPC responses in their JSON form. Large responses are
+//! parsed one item at a time, without loading them whole
+#![cfg(feature = "json")]
+
+use std::{fmt, io::Read, str::FromStr};
+
+use alloy_primitives::{hex, Address, Bytes, B256, U256};
+use alloy_rlp::RlpEncodable;
+use alloy_trie::Nibbles;
+use ethers::types::TransactionReceipt;
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde_json::Value;
+
+use crate::{
+    error::{ParseError, ProofError},
+    hasher::keccak256,
+    proof::verify_proof,
+    receipt::VerifiedReceipt,
+    transaction::AccessListItem,
+    trie::ReceiptTrieStreamer,
+};
+
+/// Read the receipts of an `eth_getBlockReceipts` response, calling
+/// `on_receipt` with each of them in block order. Returns how many were read.
+///
+/// The response may be the whole JSON-RPC object or only its `result` array.
+pub fn stream_block_receipts<R: Read>(
+    reader: R,
+    on_receipt: impl FnMut(VerifiedReceipt),
+) -> Result<usize, ParseError> {
+    let mut visitor = ReceiptsVisitor {
+        on_receipt,
+        read: 0,
+        error: None,
+    };
+
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
+    let result = deserializer
+        .deserialize_any(&mut visitor)
+        .and_then(|_| deserializer.end());
+
+    if let Some(error) = visitor.error {
+        return Err(error);
+    }
+    result.map_err(|error| ParseError::Json(error.to_string()))?;
+
+    Ok(visitor.read)
+}
+
+/// Receipts root of an `eth_getBlockReceipts` response, read as by
+/// [stream_block_receipts]
+pub fn block_receipts_root<R: Read>(reader: R) -> Result<B256, ParseError> {
+    let mut streamer = ReceiptTrieStreamer::new();
+    stream_block_receipts(reader, |receipt| streamer.add_leaf(&receipt))?;
+
+    Ok(streamer.root())
+}
+
+/// Check an `eth_getProof` response against `state_root`: the account
+/// against the state trie, then each storage slot against the storage hash
+/// of the account.
+///
+/// Missing accounts and zero slots are not in their trie, they are checked
+/// as such.
+pub fn verify_storage_proof_from_rpc(
+    proof_json: &Value,
+    state_root: B256,
+) -> Result<(), ProofError> {
+    let address: Address = field(proof_json, "address")?;
+    let account = TrieAccount {
+        nonce: field(proof_json, "nonce")?,
+        balance: field(proof_json, "balance")?,
+        storage_root: field(proof_json, "storageHash")?,
+        code_hash: field(proof_json, "codeHash")?,
+    };
+
+    verify_rpc_proof(
+        state_root,
+        keccak256(address),
+        &alloy_rlp::encode(&account),
+        account.is_empty(),
+        proof_json.get("accountProof"),
+    )?;
+
+    let storage_proofs = proof_json
+        .get("storageProof")
+        .and_then(Value::as_array)
+        .ok_or(ProofError::InvalidResponse)?;
+    for storage_proof in storage_proofs {
+        // Keys are returned as requested, they may be shorter than 32 bytes
+        let key: U256 = field(storage_proof, "key")?;
+        let value: U256 = field(storage_proof, "value")?;
+
+        verify_rpc_proof(
+            account.storage_root,
+            keccak256(B256::from(key)),
+            &alloy_rlp::encode(value),
+            value.is_zero(),
+            storage_proof.get("proof"),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Parse the `accessList` of an RPC transaction.
+///
+/// Addresses that are not 20 bytes long are rejected, rather than padded or
+/// truncated.
+pub fn parse_access_list(json: &Value) -> Result<Vec<AccessListItem>, ParseError> {
+    let invalid = |message: &str| ParseError::Json(message.to_string());
+
+    json.as_array()
+        .ok_or_else(|| invalid("access list is not an array"))?
+        .iter()
+        .map(|item| {
+            let address = item
+                .get("address")
+                .and_then(Value::as_str)
+                .ok_or_else(|| invalid("missing field `address`"))?;
+            let address = hex::decode(address).map_err(|error| invalid(&error.to_string()))?;
+            if address.len() != Address::len_bytes() {
+                return Err(ParseError::InvalidAddressLength {
+                    length: address.len(),
+                });
+            }
+
+            let storage_key = item
+                .get("storageKeys")
+                .and_then(Value::as_array)
+                .ok_or_else(|| invalid("missing field `storageKeys`"))?
+                .iter()
+                .map(|key| {
+                    key.as_str()
+                        .and_then(|key| key.parse().ok())
+                        .ok_or_else(|| invalid("invalid storage key"))
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok(AccessListItem {
+                address: Address::from_slice(&address),
+                storage_key,
+            })
+        })
+        .collect()
+}
+
+/// Account as stored in the state trie
+#[derive(RlpEncodable)]
+struct TrieAccount {
+    nonce: U256,
+    balance: U256,
+    storage_root: B256,
+    code_hash: B256,
+}
+
+impl TrieAccount {
+    /// Whether the account is the one returned for an address not in the
+    /// state
+    fn is_empty(&self) -> bool {
+        self.nonce.is_zero()
+            && self.balance.is_zero()
+            && self.storage_root == alloy_trie::EMPTY_ROOT_HASH
+            && self.code_hash == keccak256([])
+    }
+}
+
+/// Verify a proof of the response. A key not in the trie is expected when
+/// `may_be_absent`
+fn verify_rpc_proof(
+    root: B256,
+    hashed_key: B256,
+    value: &[u8],
+    may_be_absent: bool,
+    proof: Option<&Value>,
+) -> Result<(), ProofError> {
+    let proof = proof
+        .and_then(Value::as_array)
+        .ok_or(ProofError::InvalidResponse)?
+        .iter()
+        .map(|node| node.as_str().and_then(|node| Bytes::from_str(node).ok()))
+        .collect::<Option<Vec<Bytes>>>()
+        .ok_or(ProofError::InvalidResponse)?;
+
+    match verify_proof(root, &Nibbles::unpack(hashed_key), value, &proof) {
+        Err(ProofError::KeyNotFound) if may_be_absent => Ok(()),
+        result => result,
+    }
+}
+
+/// Parse the hex string field `name` of `value`
+fn field<T: FromStr>(value: &Value, name: &str) -> Result<T, ProofError> {
+    value
+        .get(name)
+        .and_then(Value::as_str)
+        .and_then(|field| field.parse().ok())
+        .ok_or(ProofError::InvalidResponse)
+}
+
+struct ReceiptsVisitor<F> {
+    on_receipt: F,
+    read: usize,
+    /// Conversion error that stopped the parsing
+    error: Option<ParseError>,
+}
+
+impl<'de, F: FnMut(VerifiedReceipt)> Visitor<'de> for &mut ReceiptsVisitor<F> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an array of receipts or a JSON-RPC response")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(receipt) = seq.next_element::<TransactionReceipt>()? {
+            let receipt = VerifiedReceipt::try_from(&receipt).map_err(|error| {
+                self.error = Some(ParseError::Receipt {
+                    index: self.read,
+                    error,
+                });
+                de::Error::custom("invalid receipt")
+            })?;
+
+            (self.on_receipt)(receipt);
+            self.read += 1;
+        }
+
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        let mut found = false;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "result" {
+                map.next_value_seed(&mut *self)?;
+                found = true;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+
+        if !found {
+            return Err(de::Error::missing_field("result"));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de, F: FnMut(VerifiedReceipt)> DeserializeSeed<'de> for &mut ReceiptsVisitor<F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256, B64, U256, U64};
+
+    use super::*;
+    use crate::{
+        block::BlockHeader, error::ReceiptError, receipt::tests::receipt_fixture,
+        trie::ordered_trie_root,
+    };
+
+    /// A receipts response as returned by a node, with the fields that are not
+    /// part of the receipt
+    fn response(receipts: &[VerifiedReceipt]) -> String {
+        let result: Vec<TransactionReceipt> = receipts.iter().map(Into::into).collect();
+
+        serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string()
+    }
+
+    /// Header of OP Sepolia block 26207960
+    fn op_sepolia_header() -> BlockHeader {
+        BlockHeader {
+            parent: b256!("caa58d7282ddb8705f65163e4ec9d960d02b1ea954f7679f8fcabbb84b2b8da5"),
+            uncles_hash: b256!("1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347"),
+            miner: address!("4200000000000000000000000000000000000011"),
+            state_root: b256!("2eef5d480dcb78a71b7d85a2d6026a03caf87f6cb2477bcb37aa63da757a6e11"),
+            transaction_root: b256!("41319534c9369ed69b3c19372a249b2a0613d7580c370d97e9da31144e31bdfc"),
+            receipts_root: b256!("2fc73e6f4c1d3bafa5dd322c53b51467af2fd26eb2d6de4fec0704804a900e54"),
+            logs_bloom: "0x0021000000040001000000008000000000000a00000800000000000020000000000000000000020000100200008000000200102000008000000200000000000000000800000000000000000a000800204000080000400002000000000000000004004000800000000080000000000000000000000000002000004010000002000000000000000000000000000100000000000000000000080000004020000000000000100000000000000000100000084000080000000081000008000040000000000002080000000020000004000000000000010000001800000000008008100000000402000000000000008001000008000000000000000010000000080000".parse().unwrap(),
+            difficulty: U256::ZERO,
+            number: U64::from(26207960),
+            gas_limit: U256::from(0x3938700),
+            gas_used: U256::from(0xe8d18),
+            timestamp: U256::from(0x67f6a95c),
+            extra_data: "0x00000000fa00000006".parse().unwrap(),
+            mix_hash: b256!("fca9613bdc740286ff7d7732e3bbaeb79228193b38f2f9fab022c9ff8c4f56a3"),
+            nonce: B64::ZERO,
+            base_fee_per_gas: Some(U256::from(0x10e)),
+            withdrawals_root: Some(alloy_trie::EMPTY_ROOT_HASH),
+            blob_gas_used: Some(U64::ZERO),
+            excess_blob_gas: Some(U64::ZERO),
+            parent_beacon_block_root: Some(b256!("789b1c07d9bad081101ab49fb48daa5abf9dce9ff2906166a0280433833e001e")),
+            requests_hash: None,
+        }
+    }
+
+    /// `eth_getBlockReceipts` response for OP Sepolia block 26207960: its L1
+    /// info deposit and 9 transactions, without the L1 fee fields. The
+    /// receipts come from executing the block with `kona-executor` against
+    /// the block's witness in its test data, which yields the block hash of
+    /// the chain.
+    #[cfg(feature = "optimism")]
+    const OP_SEPOLIA_RECEIPTS: &str = include_str!("../testdata/op_sepolia_26207960_receipts.json");
+
+    /// `eth_getProof` response for the `L1Block` predeploy of OP Sepolia at
+    /// block 26207960, with the L1 block number and timestamp (slot 0) and
+    /// the L1 block hash (slot 2). The nodes are the ones a node served as
+    /// the state witness of block 26207961, in the test data of
+    /// `kona-executor`.
+    fn op_sepolia_account_proof() -> serde_json::Value {
+        serde_json::json!({
+            "address": "0x4200000000000000000000000000000000000015",
+            "accountProof": [
+            "0xf90211a04724cb0aeba552c4d6cbe444ae4158fd63a551e0f510e485f18debd3ed96303fa0fbc5796bfd40ff60324181c3120bc76d97e1ac879513ded3e597beb40b03f3c2a0e0a7204c6346694b315413322486cb98a21713bb6fba2acdc9a4b8da4c4c2433a0ddfad51358685cb8e93bb2b2b72e08e6496d052643eb91d1e31a419b01a44d61a07f47779b69ae5e3e807650eef3e2192871e21d98348ce20cf80694f40333050aa0deb1611e619d4bc208abcdf5de96ee985bfb3a4612e7aba8ad363255a53685e8a0adf1dbfac3e6d16a23a27e996b406173f2a4971891fa81c645f8d75a8d7b973fa0eb0b4c8252f871df50ab8de004445fa2312b3297e19f65cd7eb0c36cde0a2211a07431289baad225f5562b32a90769e98f3d41922a9c81a1ce5bd931affa8dcbeda044a58654a69c014aca8ca2527b0f4e656b7f022aec2db60e11790857b6fc2db9a0073895d2a65828087569abadf807ce3755b0ae495c04669ca878502a14c885e2a0918013bcbfaf883af6b9ec1e36d0dae009aea8f4e053b3711add79a379a5d0a8a08f4f7e167998555d04b061f9b58821437da5426c2f5d989b36f729817192b4d5a0515d4e336d1caee7e536adb9c6dad1d2b6380bb1af28f780c8af6f924cacb3ffa0ab2343a2bb1eb4fe7c1381068983d5de0b4f60f378eb16bd85188daf47502d6aa0c98d485f0cecff4b63df836835e451b7fdd3633f794d52adb69589ff649cf71580",
+            "0xf90211a0cf346587fac311b52e091b5f70847bea91a7c46fab37d29d4ef8eb44b240e1f7a0ee56dbb605cf23ddcf96b81b95af5321c4be6a2adf8efdcb9cf263f1fa9b133fa062f491b0f08c8c2d4a86f7a6e8a17995c59bc08dffcb76e25e1e53a8c436f370a06b3423c243b6deccdbadebf0ef275ca309615bf0029d85b0ec33fb83d74f059da0aabaa8c001eb3a404e9b5b01275202bd0cd64e2ad6faef5160202ba02e91ea18a068d1373e4693e968cb53c7d26b17acb1f29dceb57c8f4aa023503c80ad2ee29ba03fa14f4ee0de419f4a105ea1398077b91e5a952f7bfb9a329a1c5b9cc61db0dca0fb06ace993650146093c1f1b32df60cac20dba3cb8032bfa21d684049927ff9ba04a7e3556faa469733820a7aabb69c277fe8a46d95824f9b3690b4f7697a9e558a05fe5457df2b1d6672a8d7715695483a7b61cdd1587c165645e7e3d6ad4df2963a09c74d256dfc6b4ae9d119c1f97afe4bc8dde1fde1582e8b70841a4aee383b631a06744c174f8195ece9015467e24fe9db17d7ecdb3cdad77f634151411a957f01ba0e90d97c7433080007f8cf19bbb9457208fc524771311bc7282da6ee3067d75bea06365317ea041e8ace1637bf4ac0e25a6196882774411a57b18c9c4d8f2a74740a05b5607b63a49460167e6b721fc80b6fcd89df29b0b24c9f162ac123ef973bed7a04d020a366b5372a984d8057a2a62d54dd4f02eac40d377473fbdb6946f3ab3f080",
+            "0xf90211a017c0c8ad65835c68348f06b0e1ee811ab88830ddc680e2463321931b469c1300a08f3e5410388ad65b88283e9cbf7ee0473e921809a9df3209cd968ec1422874caa0f4e97468046c8d288d3d38d77746d6abd2a6076ea731c8922cad7bc2bd786323a07d46dbabe2fd27c854b7a536a2a2853a1982c7931439912a48d137ab15f11ec6a05b30fd11472bd066caf567e4af71c31142ec310d26c6865a7e99518eb4d6e078a002dbbb9d4f571bc2f1772ec1cf97564e542d49cfd7bb3f66f4d26a45c29e2021a0d46c8de8794885a68471b668b471a383577994728ecbfb297d14cbb594b5ab3ba03154afec3531aca6e2208464cabbc962b54312cc32eb67984adf3bac72b335f2a0313b7b7a51bf82d4ff651880b2247479055255a0d86b404a0f4f177094d2d8e8a04dd701193f91272477899cace7327f14c3f89483403dfd793a0a3ba8c9984fd1a0f27133e594642837ffbb903e969561be451134ca436f4cd531528416e5696e91a008aa7484096306310be4c1ba6626a634015c045b171245e307f744a5afa915b8a071bcaccf26097e6245b346ad67cd1c1e6454e0bb21ef44271a302791fbd131f4a0e8ea97fa38d449351c45dac236417a84b98d76e26583df5a53eb539371e7e5c2a0cbb705e03965e0159aa0ceb04db190d48c0673e012c298f53bf61c191b530a79a08cef2d7f469dda12f66f4c60143774ef2a7e8b7cfb55285419d62db42107534980",
+            "0xf90211a05492b7f176993acc137827ac088a04c3984a8664ef07af85558d588da754cdc4a0b0bb957d8ff8a897acc2328d487df246b801b51f3635d2879948542ff690b6f6a0423154a563d5b56861e38e7ba52f635b63c4a3fdc0461356c0c2b1aec6af9759a03eacd9d89a17fb15333757461937a739ce4c04d0fbfae50980c512354dcb17a4a0e78c9fa4517d269599a11833453e4ce0fb2f14a01fe014664bbf975b30711e54a0ec4fe61caea689a8d14453a2dfd370030dc042053050a33037f5f42d8d4b3d02a0dd048c3a2f9ee0cc8bea8716792b1b18f3d692ffd7edb5395871c51a8c0248c8a0bded5239f44e0133cd699667ef0f2e41cadafeb44fbcca74da25fe7fe78243aca025f00e76b301e77b896e5a14ae489e8e789d16467e9889023fcc47e253fe372ea061a2c0996d47e149eb4969ea69c7689929df4b78a651ab30a37ee3063e292c8ba0f7b50a2686edf2ba54b3a834d72a8de66eb1568f4390555e72e9d503cc49f436a074e81867c272351655c84b900c525c1d2c5436f9b11f0138d6c9efd8d1613b12a0b4f5c1cee247042a994ebdc0ca7f80a7a9847a2ca884b295436ea5a768c119d1a0a5320b9d1e400f0ff37d68480c82ce17ae2b84f0fa729a29e94e13b3fc5089d0a075b54c7d0ffc94fb4de05d29b81918b2a9835db474d3805bb83744bb0601c518a0d2b49013de33332f1e1af5dd5e6037a031f18ed330b69763dd20039a0522d2cd80",
+            "0xf90211a0d56b30675717fedd1c3d7da12d8020fef2f40595e7c1a866d483ae323bac8851a00d2dfc74edc68649f6e56b1e46c0538c0667a4fb43c2b85c59e15f5248b68555a0037147485dd3758aa79afda975b71b8dffd88d514b7d5fcaae18cb2d93821400a0bfab078df25bb8ccb61d119740a1bc66bb5acebaf14bcbd6bfe450d6831a41e8a0a0b55cc3541224908d2a5d3a798d092d97b09ec02c5701873f53e52b4a798a0fa09e5e9c050429521c9adb07db0dc4ece5c3b50ecce29a4d9935191ad197f97ad3a0d77571bfcc703d23ddabc054b2cfd4e9ec976e9dc16aa46a5fd66ac837e5fc6fa09551ae90c65ac7f336282ca8635a454dc812dd7ec21fb4b9b66405237b4b4e11a0b3245775b60b5bcdfb6d985a44c689fb83c41e4de6e75ab46a2210eac3df3333a0542ad10dc7a4523b81e65a4814a48f8fb8cac4097a1a75fa2b7bab2f710fe801a0d8acdd676f8bf336c2055a5063b7d265b4fe29dd7c88177cd72c86161a873e2da04616b8d4a4cc19013cd112479d1b8fe5741443893c52743a6041938533665743a090f3d820728604c00c085f154f9b84beb9fb2b5b35178d215411b0fdd96eb6fba0d878b5f460cdb278a8eebe8b4f34569b58ffe696dca5e0373c1c900d3efa6d9ea090d184f41230d0fd0cb90ac9569da3327face4e2ea9b676a75110ee439b2ef01a0dc6c60cecef84a588e6d294f9add642109bcb01b1478c9bee15d4fa97bb5cfe280",
+            "0xf90111a02b2fc288da1b3836a887174eb6e99876293846337066072e97c4e72bb06b25ad80a0da0e696ca06d575962362e6cc444e19b86831a85d20232491556994d2b45d06b8080a03daf831715172bc0402b458b5ca6b811600b49d0a15c17f7200c9a44f792e99ba0063187f87fc180d8535e1835746d1e1bacc649f3ed9b03e029294baec9b35fba80808080a058a84cec7735088e26644665b3c7fe3d6a9a4ab7ecf8eaa20e5cbc4f0a6bc6a7a0e68eb9a668bee3ce41d7983d9eb0043872d3daab2a048c3a8ba971f98b19895880a0a5e4acd599c9ea080def1cc3df8a9a9549a40dacf0e225ea0a5082cef20798afa058e53f417220ffa4f6e59d79c2bc65945b0a11f1dfea0ca0e9eae716e5bc0aa880",
+            "0xf851a0435f16567c59d2b64a0d7ed41ad4eae78e6b4dd0f247b100b4da8318041003d680a0a3815fc65fc2fe5831bf0ca3736356230a4e23a117e9293a8833c47d2d3d4b118080808080808080808080808080",
+            "0xf8669d3b05f896ff0105268980868fa165a0822afb78240802859cc07e175bebb846f8448080a02a950119683c3cd1f4ca9c5d89a05cf35ac59fc751769bf375108db8d80ee513a01f958654ab06a152993e7a0ae7b6dbb0d4b19265cc9337b8789fe1353bd9dc35"
+            ],
+            "balance": "0x0",
+            "codeHash": "0x1f958654ab06a152993e7a0ae7b6dbb0d4b19265cc9337b8789fe1353bd9dc35",
+            "nonce": "0x0",
+            "storageHash": "0x2a950119683c3cd1f4ca9c5d89a05cf35ac59fc751769bf375108db8d80ee513",
+            "storageProof": [
+                {
+                    "key": "0x0",
+                    "value": "0x67f6a91000000000007b5f41",
+                    "proof": [
+                        "0xf90151a0686998cc03398a6a0664e0ac804312aed71671df380ab4af5b76423133cff42d80a0eb410c48050cd03b3957c74dfa8c901ea52197debac26d066d0f6561996d52ed80a0e56bc621f0b8e8b2b3047157fd4aa96058ab237fe303b93ced65b8bafb445964a0231eba9c2bc1784b944714d5260873e3f92b58434c1879123d58f995b342865180a014a0646914bb81f97bab254df79386dd2923276b6b61d6e9bb330a985859c3e1a06db8daaf327d2b95038cd08d250ffabb4c3112baecfed35889a390084c9eeef380a0c5d54b915b56a888eee4e6eeb3141e778f9b674d1d322962eed900f02c29990aa005efe19eb79ca2e8d1e5a51ab078b4bdd1840646343553d9ca889a30f962a78ba05322cbd782bc21e24834317a37b1852c3ac9776a688077b0c3520ca18c197a1b8080a0ccb97bd5db0fe68d5b2a6f45478018a2c441d1be079f30976076e1b0257aee1580",
+                        "0xefa0390decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e5638d8c67f6a91000000000007b5f41"
+                    ]
+                },
+                {
+                    "key": "0x2",
+                    "value": "0x9244875342f5e0c1624d00f249b1a7996137b1eb44bdf16396f98f76a1bec6fa",
+                    "proof": [
+                        "0xf90151a0686998cc03398a6a0664e0ac804312aed71671df380ab4af5b76423133cff42d80a0eb410c48050cd03b3957c74dfa8c901ea52197debac26d066d0f6561996d52ed80a0e56bc621f0b8e8b2b3047157fd4aa96058ab237fe303b93ced65b8bafb445964a0231eba9c2bc1784b944714d5260873e3f92b58434c1879123d58f995b342865180a014a0646914bb81f97bab254df79386dd2923276b6b61d6e9bb330a985859c3e1a06db8daaf327d2b95038cd08d250ffabb4c3112baecfed35889a390084c9eeef380a0c5d54b915b56a888eee4e6eeb3141e778f9b674d1d322962eed900f02c29990aa005efe19eb79ca2e8d1e5a51ab078b4bdd1840646343553d9ca889a30f962a78ba05322cbd782bc21e24834317a37b1852c3ac9776a688077b0c3520ca18c197a1b8080a0ccb97bd5db0fe68d5b2a6f45478018a2c441d1be079f30976076e1b0257aee1580",
+                        "0xf843a0305787fa12a823e0f2b7631cc41b3ba8828b3321ca811111fa75cd3aa3bb5acea1a09244875342f5e0c1624d00f249b1a7996137b1eb44bdf16396f98f76a1bec6fa"
+                    ]
+                }
+            ]
+        })
+    }
+
+    /// Root and proof of `target` in the trie of `leaves`
+    fn trie_proof(leaves: &[(B256, Vec<u8>)], target: B256) -> (B256, Vec<String>) {
+        let target = Nibbles::unpack(target);
+        let mut leaves: Vec<(Nibbles, &Vec<u8>)> = leaves
+            .iter()
+            .map(|(key, value)| (Nibbles::unpack(key), value))
+            .collect();
+        leaves.sort();
+
+        let mut trie = alloy_trie::HashBuilder::default().with_proof_retainer(vec![target.clone()]);
+        for (key, value) in leaves {
+            trie.add_leaf(key, value);
+        }
+        let root = trie.root();
+
+        let proof = trie
+            .take_proofs()
+            .into_iter()
+            .filter(|(path, _)| target.starts_with(path))
+            .map(|(_, node)| node.to_string())
+            .collect();
+
+        (root, proof)
+    }
+
+    #[test]
+    fn should_verify_op_sepolia_account_proof() {
+        let header = op_sepolia_header();
+        assert_eq!(
+            header.hash(),
+            b256!("db32d9ddfa5fd26c9fb0260bbfa53da88326aa2a465e34d75180564aa31b851e")
+        );
+        let state_root = header.state_root;
+        let response = op_sepolia_account_proof();
+
+        assert_eq!(verify_storage_proof_from_rpc(&response, state_root), Ok(()));
+        assert_eq!(
+            verify_storage_proof_from_rpc(&response, B256::ZERO),
+            Err(ProofError::HashMismatch { depth: 0 })
+        );
+
+        let mut tampered = response.clone();
+        tampered["balance"] = "0x1".into();
+        assert_eq!(
+            verify_storage_proof_from_rpc(&tampered, state_root),
+            Err(ProofError::ValueMismatch)
+        );
+
+        let mut tampered = response.clone();
+        tampered["storageProof"][1]["value"] = "0x1".into();
+        assert_eq!(
+            verify_storage_proof_from_rpc(&tampered, state_root),
+            Err(ProofError::ValueMismatch)
+        );
+
+        let mut malformed = response;
+        malformed["accountProof"][1] = "0xzz".into();
+        assert_eq!(
+            verify_storage_proof_from_rpc(&malformed, state_root),
+            Err(ProofError::InvalidResponse)
+        );
+    }
+
+    #[test]
+    fn should_verify_storage_proofs_from_rpc() {
+        let address = Address::repeat_byte(0x11);
+        let slot = |key: u64| keccak256(B256::from(U256::from(key)));
+        let storage = [
+            (slot(0), alloy_rlp::encode(U256::from(42))),
+            (slot(1), alloy_rlp::encode(U256::from(7))),
+        ];
+        let (storage_root, slot_proof) = trie_proof(&storage, slot(1));
+        // Slot 2 is zero, its proof shows it is not in the trie
+        let (_, zero_proof) = trie_proof(&storage, slot(2));
+
+        let account = TrieAccount {
+            nonce: U256::from(1),
+            balance: U256::from(1000),
+            storage_root,
+            code_hash: keccak256([0x60, 0x00]),
+        };
+        let state = [
+            (keccak256(address), alloy_rlp::encode(&account)),
+            (keccak256(Address::ZERO), alloy_rlp::encode(&account)),
+        ];
+        let (state_root, account_proof) = trie_proof(&state, keccak256(address));
+
+        let response = serde_json::json!({
+            "address": address.to_string(),
+            "accountProof": account_proof,
+            "balance": "0x3e8",
+            "codeHash": account.code_hash.to_string(),
+            "nonce": "0x1",
+            "storageHash": storage_root.to_string(),
+            "storageProof": [
+                { "key": "0x1", "value": "0x7", "proof": slot_proof },
+                { "key": "0x2", "value": "0x0", "proof": zero_proof },
+            ]
+        });
+        assert_eq!(verify_storage_proof_from_rpc(&response, state_root), Ok(()));
+
+        let mut tampered = response;
+        tampered["storageProof"][0]["value"] = "0x8".into();
+        assert_eq!(
+            verify_storage_proof_from_rpc(&tampered, state_root),
+            Err(ProofError::ValueMismatch)
+        );
+
+        tampered["storageProof"][0]["value"] = "0x0".into();
+        assert_eq!(
+            verify_storage_proof_from_rpc(&tampered, state_root),
+            Err(ProofError::ValueMismatch)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "optimism")]
+    fn should_stream_block_receipts_root() {
+        let header = op_sepolia_header();
+
+        let mut read = Vec::new();
+        let count =
+            stream_block_receipts(OP_SEPOLIA_RECEIPTS.as_bytes(), |receipt| read.push(receipt))
+                .unwrap();
+        assert_eq!(count, 10);
+        assert_eq!(read[0].deposit_nonce, Some(26207961));
+        assert_eq!(read[9].cumulative_gas_used, header.gas_used);
+
+        assert_eq!(
+            block_receipts_root(OP_SEPOLIA_RECEIPTS.as_bytes()),
+            Ok(header.receipts_root)
+        );
+
+        // The bare `result` array is accepted too
+        let response: Value = serde_json::from_str(OP_SEPOLIA_RECEIPTS).unwrap();
+        let result = response["result"].to_string();
+        assert_eq!(
+            block_receipts_root(result.as_bytes()),
+            Ok(header.receipts_root)
+        );
+
+        // Dropping the deposit changes the root
+        let mut receipts = response["result"].as_array().unwrap().clone();
+        receipts.remove(0);
+        let result = Value::Array(receipts).to_string();
+        assert_ne!(
+            block_receipts_root(result.as_bytes()),
+            Ok(header.receipts_root)
+        );
+    }
+
+    #[test]
+    fn should_stream_many_block_receipts() {
+        let receipts: Vec<VerifiedReceipt> = (0..200)
+            .map(|index| {
+                let mut receipt = receipt_fixture(Some(2));
+                receipt.cumulative_gas_used = U256::from(46_109 * (index + 1));
+                receipt
+            })
+            .collect();
+        let response = response(&receipts);
+
+        let mut read = Vec::new();
+        let count = stream_block_receipts(response.as_bytes(), |receipt| {
+            read.push(receipt.cumulative_gas_used)
+        })
+        .unwrap();
+        assert_eq!(count, 200);
+        assert_eq!(read[199], U256::from(46_109 * 200));
+
+        assert_eq!(
+            block_receipts_root(response.as_bytes()),
+            Ok(ordered_trie_root(&receipts))
+        );
+    }
+
+    #[test]
+    fn should_parse_access_list() {
+        let access_list = serde_json::json!([{
+            "address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+            "storageKeys": [
+                "0x0000000000000000000000000000000000000000000000000000000000000003"
+            ]
+        }]);
+        let parsed = parse_access_list(&access_list).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].storage_key, vec![B256::with_last_byte(3)]);
+
+        // 19 bytes
+        let access_list = serde_json::json!([{
+            "address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb",
+            "storageKeys": []
+        }]);
+        assert_eq!(
+            parse_access_list(&access_list).unwrap_err(),
+            ParseError::InvalidAddressLength { length: 19 }
+        );
+    }
+
+    #[test]
+    fn should_reject_invalid_receipts_response() {
+        assert!(matches!(
+            block_receipts_root(r#"{"jsonrpc":"2.0","id":1}"#.as_bytes()),
+            Err(ParseError::Json(_))
+        ));
+        assert!(matches!(
+            block_receipts_root(r#"[{"logs": 5}]"#.as_bytes()),
+            Err(ParseError::Json(_))
+        ));
+
+        // A log has at most 4 topics
+        let mut receipts = vec![TransactionReceipt::from(&receipt_fixture(None)); 2];
+        receipts[1].logs[0].topics = vec![Default::default(); 5];
+        let response = serde_json::to_string(&receipts).unwrap();
+        assert_eq!(
+            block_receipts_root(response.as_bytes()),
+            Err(ParseError::Receipt {
+                index: 1,
+                error: ReceiptError::TooManyTopics {
+                    log_index: 0,
+                    count: 5
+                }
+            })
+        );
+
+        // A type is a single byte
+        let mut receipts = vec![TransactionReceipt::from(&receipt_fixture(None)); 2];
+        receipts[0].transaction_type = Some(0x100.into());
+        let response = serde_json::to_string(&receipts).unwrap();
+        assert_eq!(
+            block_receipts_root(response.as_bytes()),
+            Err(ParseError::Receipt {
+                index: 0,
+                error: ReceiptError::TypeOutOfRange { tx_type: 0x100 }
+            })
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Verification of Merkle Patricia Trie proofs
+use alloy_primitives::{Bytes, B256};
+use alloy_rlp::{Header, EMPTY_STRING_CODE};
+use alloy_trie::Nibbles;
+
+use crate::{error::ProofError, hasher::keccak256};
+
+/// Reference to a node from its parent
+enum NodeRef<'a> {
+    Hash(B256),
+    /// Nodes shorter than 32 bytes are embedded in their parent
+    Inline(&'a [u8]),
+}
+
+/// Maximum number of nodes [verify_proof] walks through: a key of the state
+/// or storage tries is 64 nibbles long
+pub const MAX_PROOF_DEPTH: usize = 64;
+
+/// Check that `proof` shows `key` is in the trie with root `root` and value
+/// `value`.
+///
+/// `proof` contains the encoded nodes from the root to the one holding the
+/// value. Proofs deeper than [MAX_PROOF_DEPTH] are rejected.
+pub fn verify_proof(
+    root: B256,
+    key: &Nibbles,
+    value: &[u8],
+    proof: &[Bytes],
+) -> Result<(), ProofError> {
+    verify_proof_with_max_depth(root, key, value, proof, MAX_PROOF_DEPTH)
+}
+
+/// [verify_proof], walking through at most `max_depth` nodes.
+///
+/// Extension nodes with an empty path do not move along the key, so an
+/// untrusted proof could chain them without this bound.
+pub fn verify_proof_with_max_depth(
+    root: B256,
+    key: &Nibbles,
+    value: &[u8],
+    proof: &[Bytes],
+    max_depth: usize,
+) -> Result<(), ProofError> {
+    let mut expected = NodeRef::Hash(root);
+    let mut nodes = proof.iter();
+    let mut position = 0;
+    let mut depth = 0;
+
+    loop {
+        let node: &[u8] = match expected {
+            NodeRef::Hash(hash) => {
+                let node = nodes.next().ok_or(ProofError::IncompleteProof)?;
+                if keccak256(node) != hash {
+                    return Err(ProofError::HashMismatch { depth });
+                }
+                node
+            }
+            NodeRef::Inline(node) => {
+                // The proof may repeat embedded nodes
+                if nodes
+                    .as_slice()
+                    .first()
+                    .is_some_and(|next| next.as_ref() == node)
+                {
+                    nodes.next();
+                }
+                node
+            }
+        };
+        depth += 1;
+        if depth > max_depth {
+            return Err(ProofError::TooDeep { max_depth });
+        }
+
+        let items = node_items(node)?;
+        match items.len() {
+            // Branch node
+            17 => {
+                if position == key.len() {
+                    return check_value(items[16], value);
+                }
+
+                expected = child_ref(items[key[position] as usize])?;
+                position += 1;
+            }
+            // Leaf or extension node
+            2 => {
+                let (is_leaf, path) = decode_path(string_payload(items[0])?)?;
+                let remaining = &key[position..];
+
+                if is_leaf {
+                    if remaining != path.as_slice() {
+                        return Err(ProofError::KeyNotFound);
+                    }
+                    return check_value(items[1], value);
+                }
+
+                if !remaining.starts_with(&path) {
+                    return Err(ProofError::KeyNotFound);
+                }
+                expected = child_ref(items[1])?;
+                position += path.len();
+            }
+            _ => return Err(ProofError::InvalidNode),
+        }
+    }
+}
+
+/// Split an encoded node into the encodings of its items
+fn node_items(node: &[u8]) -> Result<Vec<&[u8]>, ProofError> {
+    let mut buf = node;
+    let header = Header::decode(&mut buf).map_err(|_| ProofError::InvalidNode)?;
+    if !header.list || header.payload_length != buf.len() {
+        return Err(ProofError::InvalidNode);
+    }
+
+    let mut items = Vec::new();
+    while !buf.is_empty() {
+        let mut rest = buf;
+        let item = Header::decode(&mut rest).map_err(|_| ProofError::InvalidNode)?;
+        let item_length = buf.len() - rest.len() + item.payload_length;
+
+        items.push(&buf[..item_length]);
+        buf = &buf[item_length..];
+    }
+
+    Ok(items)
+}
+
+/// Payload of an encoded string
+fn string_payload(item: &[u8]) -> Result<&[u8], ProofError> {
+    let mut buf = item;
+    let header = Header::decode(&mut buf).map_err(|_| ProofError::InvalidNode)?;
+    if header.list {
+        return Err(ProofError::InvalidNode);
+    }
+
+    Ok(&buf[..header.payload_length])
+}
+
+fn child_ref(item: &[u8]) -> Result<NodeRef<'_>, ProofError> {
+    match item.first() {
+        Some(&EMPTY_STRING_CODE) => Err(ProofError::KeyNotFound),
+        Some(first) if *first >= alloy_rlp::EMPTY_LIST_CODE => Ok(NodeRef::Inline(item)),
+        _ => {
+            let hash = string_payload(item)?;
+            if hash.len() != 32 {
+                return Err(ProofError::InvalidNode);
+            }
+            Ok(NodeRef::Hash(B256::from_slice(hash)))
+        }
+    }
+}
+
+fn check_value(item: &[u8], value: &[u8]) -> Result<(), ProofError> {
+    let stored = string_payload(item)?;
+    if stored.is_empty() {
+        Err(ProofError::KeyNotFound)
+    } else if stored != value {
+        Err(ProofError::ValueMismatch)
+    } else {
+        Ok(())
+    }
+}
+
+/// Decode the hex-prefix encoded path of a leaf or extension node
+fn decode_path(encoded: &[u8]) -> Result<(bool, Nibbles), ProofError> {
+    let nibbles = Nibbles::unpack(encoded);
+    let flag = nibbles.first().ok_or(ProofError::InvalidNode)?;
+    if flag > 3 {
+        return Err(ProofError::InvalidNode);
+    }
+
+    let is_leaf = flag & 2 != 0;
+    // An even path has a padding nibble after the flag
+    let start = if flag & 1 == 1 { 1 } else { 2 };
+    if nibbles.len() < start {
+        return Err(ProofError::InvalidNode);
+    }
+
+    Ok((is_leaf, nibbles.slice(start..)))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_rlp::Encodable;
+
+    use super::*;
+
+    /// Encode the node `[path, child]` for an already encoded `path`
+    fn node(path: &[u8], child: &[u8]) -> Bytes {
+        let mut out = Vec::new();
+        alloy_rlp::Header {
+            list: true,
+            payload_length: path.length() + child.length(),
+        }
+        .encode(&mut out);
+        path.encode(&mut out);
+        child.encode(&mut out);
+
+        out.into()
+    }
+
+    #[test]
+    fn should_reject_too_deep_proof() {
+        let key = Nibbles::unpack([0x12]);
+        let value = b"value";
+
+        // A leaf behind extensions with an empty path, which do not consume
+        // any nibble of the key
+        let mut proof = vec![node(&[0x20, 0x12], value)];
+        for _ in 0..MAX_PROOF_DEPTH {
+            let child = keccak256(&proof[0]);
+            proof.insert(0, node(&[0x00], child.as_slice()));
+        }
+        let root = keccak256(&proof[0]);
+
+        assert_eq!(
+            verify_proof(root, &key, value, &proof),
+            Err(ProofError::TooDeep {
+                max_depth: MAX_PROOF_DEPTH
+            })
+        );
+        assert_eq!(
+            verify_proof_with_max_depth(root, &key, value, &proof, MAX_PROOF_DEPTH + 1),
+            Ok(())
+        );
+    }
+}
@@ -1,24 +1,51 @@
 //! Define a receipt contained in a [Transaction](super::transaction::Transaction)
 
-use alloy_primitives::{Bloom, Log, U256};
-use alloy_rlp::{BufMut, Encodable};
+use alloy_primitives::{Bloom, Log, B256, U256};
+use alloy_rlp::{BufMut, Decodable, Encodable};
+
+use crate::{
+    error::{DecodeError, ReceiptError},
+    trie::ordered_trie_root,
+    utils::{ensure_consumed, ensure_no_trailing_bytes, take_list_payload},
+};
+
+#[cfg(feature = "optimism")]
+use crate::utils::decode_optional;
 
 /// Receipt of an executed transaction. It contains teh details of it execution.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct VerifiedReceipt {
     pub transaction_type: Option<u8>,
     pub status: bool,
     pub cumulative_gas_used: U256,
     pub logs: Vec<Log>,
     pub logs_bloom: Bloom,
+    /// Nonce of the sender of an Optimism deposit, from the Regolith upgrade
+    #[cfg(feature = "optimism")]
+    pub deposit_nonce: Option<u64>,
+    /// Version of the deposit receipt, from the Canyon upgrade
+    #[cfg(feature = "optimism")]
+    pub deposit_receipt_version: Option<u64>,
 }
 
 impl VerifiedReceipt {
+    /// Whether the transaction executed successfully (EIP-658 status)
+    pub fn is_success(&self) -> bool {
+        self.status
+    }
+
     fn payload_length(&self) -> usize {
         let mut len = self.status.length();
         len += self.cumulative_gas_used.length();
         len += self.logs_bloom.length();
-        len += self.logs.len();
+        len += self.logs.length();
+        #[cfg(feature = "optimism")]
+        {
+            len += self.deposit_nonce.map_or(0, |nonce| nonce.length());
+            len += self
+                .deposit_receipt_version
+                .map_or(0, |version| version.length());
+        }
 
         len
     }
@@ -30,7 +57,8 @@ impl VerifiedReceipt {
             payload_length,
         };
 
-        if let Some(tx_type) = self.transaction_type {
+        // Legacy receipts are bare lists, even when typed 0 as by the RPC
+        if let Some(tx_type) = self.transaction_type.filter(|tx_type| *tx_type != 0) {
             out.put_u8(tx_type);
         }
 
@@ -40,32 +68,452 @@ impl VerifiedReceipt {
         self.cumulative_gas_used.encode(out);
         self.logs_bloom.encode(out);
         self.logs.encode(out);
+        #[cfg(feature = "optimism")]
+        {
+            if let Some(nonce) = self.deposit_nonce {
+                nonce.encode(out);
+            }
+            if let Some(version) = self.deposit_receipt_version {
+                version.encode(out);
+            }
+        }
+    }
+
+    /// Decode a receipt from its EIP-2718 encoding
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let first = *buf.first().ok_or(alloy_rlp::Error::InputTooShort)?;
+
+        // Typed receipts are prefixed by the transaction type
+        let transaction_type = if first < alloy_rlp::EMPTY_LIST_CODE {
+            *buf = &buf[1..];
+            Some(first)
+        } else {
+            None
+        };
+
+        let mut payload = take_list_payload(buf)?;
+
+        let receipt = Self {
+            transaction_type,
+            status: Decodable::decode(&mut payload)?,
+            cumulative_gas_used: Decodable::decode(&mut payload)?,
+            logs_bloom: Decodable::decode(&mut payload)?,
+            logs: Decodable::decode(&mut payload)?,
+            #[cfg(feature = "optimism")]
+            deposit_nonce: decode_optional(&mut payload)?,
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: decode_optional(&mut payload)?,
+        };
+        ensure_consumed(payload)?;
+
+        Ok(receipt)
+    }
+
+    /// Decode a receipt as [Self::decode], failing with
+    /// [DecodeError::TrailingBytes] if bytes follow it
+    pub fn decode_strict(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (receipt, rest) = Self::decode_lenient(bytes)?;
+        ensure_no_trailing_bytes(rest)?;
+
+        Ok(receipt)
+    }
+
+    /// Decode a receipt as [Self::decode], returning the bytes that follow it
+    pub fn decode_lenient(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeError> {
+        let mut buf = bytes;
+        let receipt = Self::decode(&mut buf)?;
+
+        Ok((receipt, buf))
     }
 }
 
-impl From<&ethers::prelude::TransactionReceipt> for VerifiedReceipt {
-    fn from(value: &ethers::prelude::TransactionReceipt) -> Self {
+impl Encodable for VerifiedReceipt {
+    fn encode(&self, out: &mut dyn BufMut) {
+        VerifiedReceipt::encode(self, out)
+    }
+}
+
+impl TryFrom<&ethers::prelude::TransactionReceipt> for VerifiedReceipt {
+    type Error = ReceiptError;
+
+    fn try_from(value: &ethers::prelude::TransactionReceipt) -> Result<Self, Self::Error> {
         let logs = value
             .logs
             .iter()
-            .map(|log| {
+            .enumerate()
+            .map(|(log_index, log)| {
+                // A log has at most 4 topics
                 Log::new(
                     log.address.0.into(),
                     log.topics.iter().map(|topic| topic.0.into()).collect(),
                     log.data.0.clone().into(),
                 )
-                .unwrap()
+                .ok_or(ReceiptError::TooManyTopics {
+                    log_index,
+                    count: log.topics.len(),
+                })
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        Self {
+        Ok(Self {
             transaction_type: value
                 .transaction_type
-                .map(|tx_type| u8::try_from(tx_type.as_u64()).unwrap()),
-            status: value.status.map_or(false, |status| status.0[0] == 1),
+                .map(|tx_type| {
+                    let tx_type = tx_type.as_u64();
+                    u8::try_from(tx_type).map_err(|_| ReceiptError::TypeOutOfRange { tx_type })
+                })
+                .transpose()?,
+            status: value.status.is_some_and(|status| status.0[0] == 1),
             cumulative_gas_used: value.cumulative_gas_used.into(),
             logs,
             logs_bloom: value.logs_bloom.0.into(),
+            #[cfg(feature = "optimism")]
+            deposit_nonce: deposit_field(value, "depositNonce"),
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: deposit_field(value, "depositReceiptVersion"),
+        })
+    }
+}
+
+/// Rebuild an ethers receipt from the consensus fields.
+///
+/// Fields that are not part of the receipt, as the block hash, the transaction
+/// index or the gas used by this transaction alone, are left to their default.
+impl From<&VerifiedReceipt> for ethers::prelude::TransactionReceipt {
+    fn from(value: &VerifiedReceipt) -> Self {
+        let logs = value
+            .logs
+            .iter()
+            .map(|log| ethers::types::Log {
+                address: log.address.0 .0.into(),
+                topics: log.topics().iter().map(|topic| topic.0.into()).collect(),
+                data: log.data.data.to_vec().into(),
+                ..Default::default()
+            })
+            .collect();
+
+        #[allow(unused_mut)]
+        let mut receipt = Self {
+            transaction_type: value.transaction_type.map(Into::into),
+            status: Some(u64::from(value.status).into()),
+            cumulative_gas_used: value.cumulative_gas_used.into(),
+            logs,
+            logs_bloom: value.logs_bloom.0 .0.into(),
+            ..Default::default()
+        };
+
+        #[cfg(feature = "optimism")]
+        for (key, field) in [
+            ("depositNonce", value.deposit_nonce),
+            ("depositReceiptVersion", value.deposit_receipt_version),
+        ] {
+            if let Some(field) = field {
+                receipt
+                    .other
+                    .insert(key.into(), format!("{field:#x}").into());
+            }
+        }
+
+        receipt
+    }
+}
+
+/// Read a deposit field that ethers does not know about
+#[cfg(feature = "optimism")]
+fn deposit_field(receipt: &ethers::prelude::TransactionReceipt, key: &str) -> Option<u64> {
+    receipt
+        .other
+        .get_deserialized::<ethers::types::U64>(key)
+        .and_then(Result::ok)
+        .map(|value| value.as_u64())
+}
+
+/// Decode the receipts of a block from an RLP list, as in the devp2p
+/// `Receipts` message. Typed receipts are wrapped in a byte string.
+pub fn decode_block_receipts(bytes: &[u8]) -> Result<Vec<VerifiedReceipt>, DecodeError> {
+    let mut buf = bytes;
+    let mut payload = take_list_payload(&mut buf)?;
+    ensure_consumed(buf)?;
+
+    let mut receipts = Vec::new();
+    while !payload.is_empty() {
+        let receipt = if payload[0] >= alloy_rlp::EMPTY_LIST_CODE {
+            VerifiedReceipt::decode(&mut payload)?
+        } else {
+            let header = alloy_rlp::Header::decode(&mut payload)?;
+            if header.list {
+                return Err(alloy_rlp::Error::UnexpectedList.into());
+            }
+
+            let (mut typed, rest) = payload.split_at(header.payload_length);
+            payload = rest;
+
+            let receipt = VerifiedReceipt::decode(&mut typed)?;
+            ensure_consumed(typed)?;
+
+            receipt
+        };
+
+        receipts.push(receipt);
+    }
+
+    Ok(receipts)
+}
+
+/// Root of the receipts trie
+pub fn receipts_root(receipts: &[VerifiedReceipt]) -> B256 {
+    ordered_trie_root(receipts)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use alloy_primitives::{address, b256, Bytes};
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+    use crate::transaction::tests::{any_address, any_b256, any_bytes, any_u256};
+
+    fn any_receipt() -> impl Strategy<Value = VerifiedReceipt> {
+        let log = (any_address(), vec(any_b256(), 0..=4), any_bytes())
+            .prop_map(|(address, topics, data)| Log::new(address, topics, data).unwrap());
+
+        (
+            prop::option::of(0..alloy_rlp::EMPTY_LIST_CODE),
+            any::<bool>(),
+            any_u256(),
+            vec(log, 0..4),
+            any::<[u8; 256]>(),
+            prop::option::of((any::<u64>(), prop::option::of(any::<u64>()))),
+        )
+            .prop_map(
+                |(transaction_type, status, cumulative_gas_used, logs, logs_bloom, _deposit)| {
+                    VerifiedReceipt {
+                        transaction_type,
+                        status,
+                        cumulative_gas_used,
+                        logs,
+                        logs_bloom: Bloom::from(logs_bloom),
+                        // The version is only encoded after a nonce
+                        #[cfg(feature = "optimism")]
+                        deposit_nonce: _deposit.map(|(nonce, _)| nonce),
+                        #[cfg(feature = "optimism")]
+                        deposit_receipt_version: _deposit.and_then(|(_, version)| version),
+                    }
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn should_decode_what_is_encoded(receipt in any_receipt()) {
+            let mut buffer = Vec::<u8>::new();
+            receipt.encode(&mut buffer);
+
+            let mut input = buffer.as_slice();
+            let decoded = VerifiedReceipt::decode(&mut input).unwrap();
+            prop_assert!(input.is_empty());
+
+            let mut encoded = Vec::<u8>::new();
+            decoded.encode(&mut encoded);
+            prop_assert_eq!(encoded, buffer);
+        }
+
+        #[test]
+        fn should_decode_arbitrary_bytes_without_panicking(bytes in vec(any::<u8>(), 0..256)) {
+            let _ = VerifiedReceipt::decode(&mut bytes.as_slice());
+        }
+    }
+
+    /// A successful receipt with an ERC-20 `Transfer` log
+    pub(crate) fn receipt_fixture(transaction_type: Option<u8>) -> VerifiedReceipt {
+        let log = Log::new(
+            address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+            vec![
+                b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"),
+                b256!("00000000000000000000000095222290dd7278aa3ddd389cc1e1d165cc4bafe5"),
+                b256!("000000000000000000000000a69babef1ca67a37ffaf7a485dfff3382056e78c"),
+            ],
+            Bytes::from(U256::from(1_000_000).to_be_bytes_vec()),
+        )
+        .unwrap();
+
+        let mut logs_bloom = Bloom::ZERO;
+        logs_bloom.accrue_log(&log);
+
+        VerifiedReceipt {
+            transaction_type,
+            status: true,
+            cumulative_gas_used: U256::from(46_109),
+            logs: vec![log],
+            logs_bloom,
+            #[cfg(feature = "optimism")]
+            deposit_nonce: None,
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: None,
+        }
+    }
+
+    #[test]
+    fn should_decode_block_receipts() {
+        let receipts = [
+            receipt_fixture(None),
+            receipt_fixture(Some(2)),
+            receipt_fixture(Some(1)),
+        ];
+
+        // Typed receipts are wrapped in a byte string
+        let mut items = Vec::new();
+        for receipt in &receipts {
+            let mut encoded = Vec::new();
+            receipt.encode(&mut encoded);
+
+            if receipt.transaction_type.is_some() {
+                encoded.as_slice().encode(&mut items);
+            } else {
+                items.extend_from_slice(&encoded);
+            }
+        }
+        let mut bytes = Vec::new();
+        alloy_rlp::Header {
+            list: true,
+            payload_length: items.len(),
+        }
+        .encode(&mut bytes);
+        bytes.extend_from_slice(&items);
+
+        let decoded = decode_block_receipts(&bytes).unwrap();
+
+        assert_eq!(decoded.len(), 3);
+        assert_eq!(decoded[1].transaction_type, Some(2));
+        assert_eq!(decoded[0].logs, receipts[0].logs);
+        assert_eq!(receipts_root(&decoded), receipts_root(&receipts));
+    }
+
+    #[test]
+    fn should_not_prefix_type_0_receipt() {
+        // Legacy receipt of EIP-2481
+        let expected: Bytes = "0xf901668001b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f85ff85d940000000000000000000000000000000000000011f842a0000000000000000000000000000000000000000000000000000000000000deada0000000000000000000000000000000000000000000000000000000000000beef830100ff".parse().unwrap();
+        let receipt = |transaction_type| VerifiedReceipt {
+            transaction_type,
+            status: false,
+            cumulative_gas_used: U256::from(1),
+            logs: vec![Log::new(
+                address!("0000000000000000000000000000000000000011"),
+                vec![
+                    b256!("000000000000000000000000000000000000000000000000000000000000dead"),
+                    b256!("000000000000000000000000000000000000000000000000000000000000beef"),
+                ],
+                Bytes::from_static(&[0x01, 0x00, 0xff]),
+            )
+            .unwrap()],
+            logs_bloom: Bloom::ZERO,
+            #[cfg(feature = "optimism")]
+            deposit_nonce: None,
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: None,
+        };
+
+        for transaction_type in [None, Some(0)] {
+            let encoded = alloy_rlp::encode(receipt(transaction_type));
+            assert_eq!(encoded, expected.to_vec());
+            assert_eq!(
+                crate::hasher::keccak256(&encoded),
+                crate::hasher::keccak256(&expected)
+            );
         }
     }
+
+    #[test]
+    fn should_reject_log_with_too_many_topics() {
+        let log = ethers::types::Log {
+            topics: vec![ethers::types::H256::zero(); 5],
+            ..Default::default()
+        };
+        let receipt = ethers::types::TransactionReceipt {
+            logs: vec![ethers::types::Log::default(), log],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            VerifiedReceipt::try_from(&receipt).unwrap_err(),
+            ReceiptError::TooManyTopics {
+                log_index: 1,
+                count: 5
+            }
+        );
+    }
+
+    #[test]
+    fn should_encode_log_with_empty_data() {
+        // An event without non-indexed parameters
+        let log = ethers::types::Log {
+            address: "0x1111111111111111111111111111111111111111"
+                .parse()
+                .unwrap(),
+            topics: vec![ethers::types::H256::repeat_byte(0xaa)],
+            ..Default::default()
+        };
+        let receipt = ethers::types::TransactionReceipt {
+            status: Some(1.into()),
+            cumulative_gas_used: 21000.into(),
+            logs: vec![log],
+            ..Default::default()
+        };
+
+        let receipt = VerifiedReceipt::try_from(&receipt).unwrap();
+        assert!(receipt.logs[0].data.data.is_empty());
+
+        let mut buffer = Vec::<u8>::new();
+        receipt.encode(&mut buffer);
+
+        // `[address, [topic], data]`, the empty data is `0x80`
+        let encoded_log: Bytes = "0xf838941111111111111111111111111111111111111111e1a0aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa80".parse().unwrap();
+        assert!(buffer.ends_with(&encoded_log));
+
+        let decoded = VerifiedReceipt::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded.logs, receipt.logs);
+    }
+
+    #[test]
+    fn should_round_trip_ethers_receipt() {
+        for receipt in [receipt_fixture(None), receipt_fixture(Some(2))] {
+            let ethers_receipt = ethers::prelude::TransactionReceipt::from(&receipt);
+            assert_eq!(ethers_receipt.block_hash, None);
+
+            let converted = VerifiedReceipt::try_from(&ethers_receipt).unwrap();
+            assert_eq!(converted.transaction_type, receipt.transaction_type);
+            assert_eq!(converted.status, receipt.status);
+            assert_eq!(converted.cumulative_gas_used, receipt.cumulative_gas_used);
+            assert_eq!(converted.logs, receipt.logs);
+            assert_eq!(converted.logs_bloom, receipt.logs_bloom);
+            #[cfg(feature = "optimism")]
+            assert_eq!(converted.deposit_nonce, receipt.deposit_nonce);
+        }
+
+        let mut failed = receipt_fixture(Some(1));
+        failed.status = false;
+        let ethers_receipt = ethers::prelude::TransactionReceipt::from(&failed);
+        assert_eq!(ethers_receipt.status, Some(0.into()));
+        assert!(!VerifiedReceipt::try_from(&ethers_receipt).unwrap().status);
+    }
+
+    #[cfg(feature = "optimism")]
+    #[test]
+    fn should_round_trip_deposit_receipt() {
+        let mut receipt = receipt_fixture(Some(0x7e));
+        receipt.deposit_nonce = Some(22211221);
+        receipt.deposit_receipt_version = Some(1);
+
+        let mut buffer = Vec::<u8>::new();
+        receipt.encode(&mut buffer);
+
+        let decoded = VerifiedReceipt::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded.deposit_nonce, Some(22211221));
+        assert_eq!(decoded.deposit_receipt_version, Some(1));
+
+        let ethers_receipt = ethers::prelude::TransactionReceipt::from(&receipt);
+        let converted = VerifiedReceipt::try_from(&ethers_receipt).unwrap();
+        assert_eq!(converted.deposit_nonce, Some(22211221));
+        assert_eq!(converted.deposit_receipt_version, Some(1));
+    }
 }
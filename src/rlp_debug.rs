@@ -0,0 +1,91 @@
+//! Inspection of arbitrary RLP, to see the structure of an encoding when a
+//! hash does not match
+use alloy_rlp::Header;
+
+/// Lists nested deeper than this are not described
+pub const MAX_DEPTH: usize = 64;
+
+/// Item of an RLP encoding, as described by [describe]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RlpNode {
+    Str(Vec<u8>),
+    List(Vec<RlpNode>),
+    /// Bytes that could not be described, from the first malformed item
+    Invalid(Vec<u8>),
+}
+
+/// Describe the RLP item encoded in `bytes`.
+///
+/// Typed transactions and receipts are prefixed by their type, which must
+/// be stripped first. If `bytes` is not a single item, it is all
+/// [RlpNode::Invalid].
+pub fn describe(bytes: &[u8]) -> RlpNode {
+    let mut buf = bytes;
+
+    match describe_item(&mut buf, 0) {
+        Some(node) if buf.is_empty() => node,
+        _ => RlpNode::Invalid(bytes.to_vec()),
+    }
+}
+
+/// Describe the item at the start of `buf`, advancing `buf` past it
+fn describe_item(buf: &mut &[u8], depth: usize) -> Option<RlpNode> {
+    let header = Header::decode(buf).ok()?;
+    if buf.len() < header.payload_length {
+        return None;
+    }
+
+    let (payload, rest) = buf.split_at(header.payload_length);
+    *buf = rest;
+
+    if !header.list {
+        return Some(RlpNode::Str(payload.to_vec()));
+    }
+    if depth == MAX_DEPTH {
+        return None;
+    }
+
+    let mut payload = payload;
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let remaining = payload;
+        match describe_item(&mut payload, depth + 1) {
+            Some(item) => items.push(item),
+            None => {
+                items.push(RlpNode::Invalid(remaining.to_vec()));
+                break;
+            }
+        }
+    }
+
+    Some(RlpNode::List(items))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::{tests::legacy_fixture, VerifiedTransaction};
+
+    #[test]
+    fn should_describe_legacy_transaction() {
+        let mut buffer = Vec::new();
+        VerifiedTransaction::Legacy(legacy_fixture()).encode(&mut buffer);
+
+        let RlpNode::List(fields) = describe(&buffer) else {
+            panic!("a legacy transaction is a list");
+        };
+        assert_eq!(fields.len(), 9);
+        assert!(fields.iter().all(|field| matches!(field, RlpNode::Str(_))));
+
+        // A non canonical single byte, in a list
+        assert_eq!(
+            describe(&[0xc3, 0x01, 0x81, 0x00]),
+            RlpNode::List(vec![
+                RlpNode::Str(vec![0x01]),
+                RlpNode::Invalid(vec![0x81, 0x00])
+            ])
+        );
+        // Truncated
+        assert_eq!(describe(&[0xc1]), RlpNode::Invalid(vec![0xc1]));
+    }
+}
@@ -0,0 +1,116 @@
+//! SSZ merkleization, used by the beacon chain to commit to execution payloads
+#![cfg(feature = "beacon")]
+
+use alloy_primitives::{Address, B256, U256};
+use sha2::{Digest, Sha256};
+
+/// Maximum number of transactions in an execution payload
+pub const MAX_TRANSACTIONS_PER_PAYLOAD: usize = 1 << 20;
+
+/// Maximum size in bytes of a transaction in an execution payload
+pub const MAX_BYTES_PER_TRANSACTION: usize = 1 << 30;
+
+/// Maximum number of withdrawals in an execution payload
+pub const MAX_WITHDRAWALS_PER_PAYLOAD: usize = 16;
+
+/// Maximum size in bytes of the extra data of an execution payload
+pub const MAX_EXTRA_DATA_BYTES: usize = 32;
+
+fn hash_pair(left: &B256, right: &B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+
+    B256::new(hasher.finalize().into())
+}
+
+/// Root of the binary tree of `chunks`, padded with zero chunks up to `limit`
+/// leaves
+pub fn merkleize(chunks: &[B256], limit: usize) -> B256 {
+    let depth = limit.next_power_of_two().trailing_zeros();
+
+    // Root of a subtree of zero chunks, at the current level
+    let mut zero = B256::ZERO;
+    let mut layer = chunks.to_vec();
+    for _ in 0..depth {
+        if layer.len() % 2 == 1 {
+            layer.push(zero);
+        }
+        layer = layer
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+        zero = hash_pair(&zero, &zero);
+    }
+
+    layer.first().copied().unwrap_or(zero)
+}
+
+/// Mix the length of a list in its root
+pub fn mix_in_length(root: B256, length: usize) -> B256 {
+    let mut chunk = B256::ZERO;
+    chunk[..8].copy_from_slice(&(length as u64).to_le_bytes());
+
+    hash_pair(&root, &chunk)
+}
+
+/// Split bytes in 32 bytes chunks, padding the last one with zeros
+pub fn pack_bytes(bytes: &[u8]) -> Vec<B256> {
+    bytes
+        .chunks(32)
+        .map(|chunk| {
+            let mut packed = B256::ZERO;
+            packed[..chunk.len()].copy_from_slice(chunk);
+            packed
+        })
+        .collect()
+}
+
+/// Root of a `ByteList[max_length]`
+pub fn byte_list_root(bytes: &[u8], max_length: usize) -> B256 {
+    let limit = max_length.div_ceil(32);
+
+    mix_in_length(merkleize(&pack_bytes(bytes), limit), bytes.len())
+}
+
+/// Root of a `uint64`
+pub fn u64_root(value: u64) -> B256 {
+    let mut chunk = B256::ZERO;
+    chunk[..8].copy_from_slice(&value.to_le_bytes());
+
+    chunk
+}
+
+/// Root of a `uint256`
+pub fn u256_root(value: U256) -> B256 {
+    B256::new(value.to_le_bytes())
+}
+
+/// Root of an `ExecutionAddress`, a `Bytes20`
+pub fn address_root(address: Address) -> B256 {
+    let mut chunk = B256::ZERO;
+    chunk[..20].copy_from_slice(address.as_slice());
+
+    chunk
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::b256;
+
+    use super::*;
+
+    #[test]
+    fn should_merkleize_zero_chunks() {
+        // `hash(0 || 0)`, the root of two zero chunks
+        let zero_root = b256!("f5a5fd42d16a20302798ef6ed309979b43003d2320d9f0e8ea9831a92759fb4b");
+
+        assert_eq!(merkleize(&[], 1), B256::ZERO);
+        assert_eq!(merkleize(&[], 2), zero_root);
+        assert_eq!(merkleize(&[B256::ZERO], 2), zero_root);
+        assert_eq!(
+            merkleize(&[B256::ZERO; 3], 4),
+            hash_pair(&zero_root, &zero_root)
+        );
+    }
+}
@@ -1,27 +1,63 @@
 //! Different transaction types in Ethereum
 
-use alloy_primitives::{Address, Bytes, ChainId, B256, U256, U64};
-use alloy_rlp::{BufMut, Encodable, RlpDecodable, RlpEncodable};
-use ethers::types::{TransactionReceipt, U64 as EU64};
+use std::collections::HashMap;
 
-use crate::receipt::VerifiedReceipt;
+use alloy_primitives::{
+    Address, Bloom, Bytes, ChainId, FixedBytes, Log, Selector, TxHash, TxKind, B256, U256, U64,
+};
+use alloy_rlp::{BufMut, Decodable, Encodable, RlpDecodable, RlpEncodable};
+use ethers::types::{TransactionReceipt, H256 as EH256, U256 as EU256, U64 as EU64};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::{DecodeError, EncodeError, TransactionError},
+    hasher::keccak256,
+    receipt::VerifiedReceipt,
+    utils::{ensure_consumed, ensure_no_trailing_bytes, take_list_payload},
+};
 
 #[derive(Debug)]
 pub enum VerifiedTransaction {
     Legacy(TxLegacy),
     Eip2930(Tx2930),
     Eip1559(Tx1559),
+    Eip4844(Tx4844),
+    Eip7702(Tx7702),
+    #[cfg(feature = "optimism")]
+    Deposit(TxDeposit),
+    #[cfg(feature = "arbitrum")]
+    ArbitrumRetry(TxArbitrumRetry),
 }
 
 impl VerifiedTransaction {
-    pub fn new(transaction: &ethers::types::Transaction, receipt: &TransactionReceipt) -> Self {
+    pub fn new(
+        transaction: &ethers::types::Transaction,
+        receipt: &TransactionReceipt,
+    ) -> Result<Self, TransactionError> {
+        let mut txn = Self::new_without_receipt(transaction)?;
+        *txn.receipt_mut() = VerifiedReceipt::try_from(receipt)?;
+
+        Ok(txn)
+    }
+
+    /// Build a transaction when its receipt is not known, as to verify only
+    /// the transactions root. The receipt is left empty.
+    pub fn new_without_receipt(
+        transaction: &ethers::types::Transaction,
+    ) -> Result<Self, TransactionError> {
+        let missing = |field| TransactionError::MissingField { field };
+
         match transaction.transaction_type {
-            Some(EU64([0])) => {
+            // Nodes omit the type of transactions from before Berlin
+            Some(EU64([0])) | None => {
                 let txn = TxLegacy {
                     nonce: transaction.nonce.as_u64(),
-                    gas_price: transaction.gas_price.unwrap().as_u128(),
+                    gas_price: fee_to_u128(
+                        transaction.gas_price.ok_or(missing("gasPrice"))?,
+                        "gas_price",
+                    )?,
                     gas_limit: transaction.gas.as_u64(),
-                    to: Address::from(transaction.to.unwrap().0),
+                    to: transaction.to.map(|to| Address::from(to.0)).into(),
                     value: transaction.value.into(),
                     data: Bytes::from(transaction.input.0.clone()),
                     signature: Signature {
@@ -29,9 +65,9 @@ impl VerifiedTransaction {
                         r: transaction.r.into(),
                         s: transaction.s.into(),
                     },
-                    receipt: VerifiedReceipt::from(receipt),
+                    receipt: VerifiedReceipt::default(),
                 };
-                VerifiedTransaction::Legacy(txn)
+                Ok(VerifiedTransaction::Legacy(txn))
             }
             Some(EU64([1])) => {
                 let access_list: Option<Vec<AccessListItem>> =
@@ -50,23 +86,25 @@ impl VerifiedTransaction {
                     });
 
                 let txn = Tx2930 {
-                    tx_type: 1,
-                    chain_id: transaction.chain_id.unwrap().as_u64(),
+                    chain_id: transaction.chain_id.ok_or(missing("chainId"))?.as_u64(),
                     nonce: transaction.nonce.as_u64(),
-                    gas_price: transaction.gas_price.unwrap().as_u128(),
+                    gas_price: fee_to_u128(
+                        transaction.gas_price.ok_or(missing("gasPrice"))?,
+                        "gas_price",
+                    )?,
                     gas_limit: transaction.gas.as_u64(),
-                    to: Address::from(transaction.to.unwrap().0),
+                    to: transaction.to.map(|to| Address::from(to.0)).into(),
                     value: transaction.value.into(),
                     data: Bytes::from(transaction.input.0.clone()),
-                    access_list: access_list.unwrap(),
+                    access_list: access_list.ok_or(missing("accessList"))?,
                     signature: Signature {
                         v: U256::from(U64::from_limbs(transaction.v.0)),
                         r: transaction.r.into(),
                         s: transaction.s.into(),
                     },
-                    receipt: VerifiedReceipt::from(receipt),
+                    receipt: VerifiedReceipt::default(),
                 };
-                VerifiedTransaction::Eip2930(txn)
+                Ok(VerifiedTransaction::Eip2930(txn))
             }
             Some(EU64([2])) => {
                 let access_list: Option<Vec<AccessListItem>> =
@@ -85,29 +123,220 @@ impl VerifiedTransaction {
                     });
 
                 let txn = Tx1559 {
-                    tx_type: 2,
-                    chain_id: transaction.chain_id.unwrap().as_u64(),
+                    chain_id: transaction.chain_id.ok_or(missing("chainId"))?.as_u64(),
+                    nonce: transaction.nonce.as_u64(),
+                    gas_limit: transaction.gas.as_u64(),
+                    to: transaction.to.map(|to| Address::from(to.0)).into(),
+                    value: transaction.value.into(),
+                    data: Bytes::from(transaction.input.0.clone()),
+                    access_list: access_list.ok_or(missing("accessList"))?,
+                    max_fee_per_gas: fee_to_u128(
+                        transaction.max_fee_per_gas.ok_or(missing("maxFeePerGas"))?,
+                        "max_fee_per_gas",
+                    )?,
+                    max_priority_fee_per_gas: fee_to_u128(
+                        transaction
+                            .max_priority_fee_per_gas
+                            .ok_or(missing("maxPriorityFeePerGas"))?,
+                        "max_priority_fee_per_gas",
+                    )?,
+                    signature: Signature {
+                        v: U256::from(U64::from_limbs(transaction.v.0)),
+                        r: transaction.r.into(),
+                        s: transaction.s.into(),
+                    },
+                    receipt: VerifiedReceipt::default(),
+                };
+                Ok(VerifiedTransaction::Eip1559(txn))
+            }
+            Some(EU64([3])) => {
+                let access_list: Option<Vec<AccessListItem>> =
+                    transaction.access_list.clone().map(|list| {
+                        list.0
+                            .iter()
+                            .map(|item| AccessListItem {
+                                address: Address::from(item.address.0),
+                                storage_key: item
+                                    .storage_keys
+                                    .iter()
+                                    .map(|key| key.0.into())
+                                    .collect(),
+                            })
+                            .collect()
+                    });
+
+                // Blob fields are not known by ethers, they end up in `other`
+                let max_fee_per_blob_gas: EU256 = required(
+                    transaction.other.get_deserialized("maxFeePerBlobGas"),
+                    "maxFeePerBlobGas",
+                )?;
+                let blob_versioned_hashes: Vec<EH256> = required(
+                    transaction.other.get_deserialized("blobVersionedHashes"),
+                    "blobVersionedHashes",
+                )?;
+
+                let txn = Tx4844 {
+                    chain_id: transaction.chain_id.ok_or(missing("chainId"))?.as_u64(),
                     nonce: transaction.nonce.as_u64(),
                     gas_limit: transaction.gas.as_u64(),
-                    to: Address::from(transaction.to.unwrap().0),
+                    to: Address::from(transaction.to.ok_or(missing("to"))?.0),
                     value: transaction.value.into(),
                     data: Bytes::from(transaction.input.0.clone()),
-                    access_list: access_list.unwrap(),
-                    max_fee_per_gas: transaction.max_fee_per_gas.unwrap().as_u128(),
-                    max_priority_fee_per_gas: transaction
-                        .max_priority_fee_per_gas
-                        .unwrap()
-                        .as_u128(),
+                    access_list: access_list.ok_or(missing("accessList"))?,
+                    max_fee_per_gas: fee_to_u128(
+                        transaction.max_fee_per_gas.ok_or(missing("maxFeePerGas"))?,
+                        "max_fee_per_gas",
+                    )?,
+                    max_priority_fee_per_gas: fee_to_u128(
+                        transaction
+                            .max_priority_fee_per_gas
+                            .ok_or(missing("maxPriorityFeePerGas"))?,
+                        "max_priority_fee_per_gas",
+                    )?,
+                    max_fee_per_blob_gas: fee_to_u128(
+                        max_fee_per_blob_gas,
+                        "max_fee_per_blob_gas",
+                    )?,
+                    blob_versioned_hashes: blob_versioned_hashes
+                        .iter()
+                        .map(|hash| hash.0.into())
+                        .collect(),
                     signature: Signature {
                         v: U256::from(U64::from_limbs(transaction.v.0)),
                         r: transaction.r.into(),
                         s: transaction.s.into(),
                     },
-                    receipt: VerifiedReceipt::from(receipt),
+                    receipt: VerifiedReceipt::default(),
+                };
+                Ok(VerifiedTransaction::Eip4844(txn))
+            }
+            Some(EU64([4])) => {
+                let access_list: Option<Vec<AccessListItem>> =
+                    transaction.access_list.clone().map(|list| {
+                        list.0
+                            .iter()
+                            .map(|item| AccessListItem {
+                                address: Address::from(item.address.0),
+                                storage_key: item
+                                    .storage_keys
+                                    .iter()
+                                    .map(|key| key.0.into())
+                                    .collect(),
+                            })
+                            .collect()
+                    });
+
+                // Authorizations are not known by ethers, they end up in `other`
+                let authorization_list: Vec<HashMap<String, String>> = required(
+                    transaction.other.get_deserialized("authorizationList"),
+                    "authorizationList",
+                )?;
+
+                let txn = Tx7702 {
+                    chain_id: transaction.chain_id.ok_or(missing("chainId"))?.as_u64(),
+                    nonce: transaction.nonce.as_u64(),
+                    gas_limit: transaction.gas.as_u64(),
+                    to: Address::from(transaction.to.ok_or(missing("to"))?.0),
+                    value: transaction.value.into(),
+                    data: Bytes::from(transaction.input.0.clone()),
+                    access_list: access_list.ok_or(missing("accessList"))?,
+                    max_fee_per_gas: fee_to_u128(
+                        transaction.max_fee_per_gas.ok_or(missing("maxFeePerGas"))?,
+                        "max_fee_per_gas",
+                    )?,
+                    max_priority_fee_per_gas: fee_to_u128(
+                        transaction
+                            .max_priority_fee_per_gas
+                            .ok_or(missing("maxPriorityFeePerGas"))?,
+                        "max_priority_fee_per_gas",
+                    )?,
+                    authorization_list: authorization_list
+                        .iter()
+                        .map(Authorization::from_rpc)
+                        .collect::<Result<Vec<_>, _>>()?,
+                    signature: Signature {
+                        v: U256::from(U64::from_limbs(transaction.v.0)),
+                        r: transaction.r.into(),
+                        s: transaction.s.into(),
+                    },
+                    receipt: VerifiedReceipt::default(),
+                };
+                Ok(VerifiedTransaction::Eip7702(txn))
+            }
+            #[cfg(feature = "optimism")]
+            Some(EU64([DEPOSIT_TX_TYPE_U64])) => {
+                let source_hash: EH256 = required(
+                    transaction.other.get_deserialized("sourceHash"),
+                    "sourceHash",
+                )?;
+                // Missing before the Regolith upgrade
+                let mint: Option<EU256> = transaction
+                    .other
+                    .get_deserialized("mint")
+                    .transpose()
+                    .map_err(|_| TransactionError::InvalidField { field: "mint" })?;
+                let is_system_transaction: bool = transaction
+                    .other
+                    .get_deserialized("isSystemTx")
+                    .transpose()
+                    .map_err(|_| TransactionError::InvalidField {
+                        field: "isSystemTx",
+                    })?
+                    .unwrap_or_default();
+
+                let txn = TxDeposit {
+                    source_hash: source_hash.0.into(),
+                    from: Address::from(transaction.from.0),
+                    to: transaction.to.map(|to| Address::from(to.0)).into(),
+                    mint: mint
+                        .map(|mint| fee_to_u128(mint, "mint"))
+                        .transpose()?
+                        .unwrap_or_default(),
+                    value: transaction.value.into(),
+                    gas_limit: transaction.gas.as_u64(),
+                    is_system_transaction,
+                    data: Bytes::from(transaction.input.0.clone()),
+                    receipt: VerifiedReceipt::default(),
+                };
+                Ok(VerifiedTransaction::Deposit(txn))
+            }
+            #[cfg(feature = "arbitrum")]
+            Some(EU64([ARBITRUM_RETRY_TX_TYPE_U64])) => {
+                // Retry fields are not known by ethers, they end up in `other`
+                let ticket_id: EH256 =
+                    required(transaction.other.get_deserialized("ticketId"), "ticketId")?;
+                let refund_to: ethers::types::Address =
+                    required(transaction.other.get_deserialized("refundTo"), "refundTo")?;
+                let max_refund: EU256 =
+                    required(transaction.other.get_deserialized("maxRefund"), "maxRefund")?;
+                let submission_fee_refund: EU256 = required(
+                    transaction.other.get_deserialized("submissionFeeRefund"),
+                    "submissionFeeRefund",
+                )?;
+
+                let txn = TxArbitrumRetry {
+                    chain_id: transaction.chain_id.ok_or(missing("chainId"))?.as_u64(),
+                    nonce: transaction.nonce.as_u64(),
+                    from: Address::from(transaction.from.0),
+                    gas_fee_cap: fee_to_u128(
+                        transaction.max_fee_per_gas.ok_or(missing("maxFeePerGas"))?,
+                        "gas_fee_cap",
+                    )?,
+                    gas_limit: transaction.gas.as_u64(),
+                    to: transaction.to.map(|to| Address::from(to.0)).into(),
+                    value: transaction.value.into(),
+                    data: Bytes::from(transaction.input.0.clone()),
+                    ticket_id: ticket_id.0.into(),
+                    refund_to: Address::from(refund_to.0),
+                    max_refund: max_refund.into(),
+                    submission_fee_refund: submission_fee_refund.into(),
+                    receipt: VerifiedReceipt::default(),
                 };
-                VerifiedTransaction::Eip1559(txn)
+                Ok(VerifiedTransaction::ArbitrumRetry(txn))
             }
-            _ => panic!("Unknown transaction type"),
+            Some(tx_type) => Err(TransactionError::UnknownType {
+                tx_type: tx_type.as_u64(),
+            }),
         }
     }
 
@@ -116,6 +345,616 @@ impl VerifiedTransaction {
             Self::Legacy(txn) => txn.encode(out),
             Self::Eip1559(txn) => txn.encode(out),
             Self::Eip2930(txn) => txn.encode(out),
+            Self::Eip4844(txn) => txn.encode(out),
+            Self::Eip7702(txn) => txn.encode(out),
+            #[cfg(feature = "optimism")]
+            Self::Deposit(txn) => txn.encode(out),
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => txn.encode(out),
+        }
+    }
+
+    /// Encode the transaction after checking the invariants of its type.
+    ///
+    /// Blob transactions can't create contracts, which [Tx4844] already
+    /// enforces with a non optional `to`.
+    pub fn try_encode(&self, out: &mut dyn BufMut) -> Result<(), EncodeError> {
+        let chain_id = match self {
+            Self::Eip2930(txn) => Some(txn.chain_id),
+            Self::Eip1559(txn) => Some(txn.chain_id),
+            Self::Eip4844(txn) => Some(txn.chain_id),
+            Self::Eip7702(txn) => Some(txn.chain_id),
+            _ => None,
+        };
+        if chain_id == Some(0) {
+            return Err(EncodeError::MissingChainId);
+        }
+
+        if let Self::Eip4844(txn) = self {
+            if txn.blob_versioned_hashes.is_empty() {
+                return Err(EncodeError::NoBlobHashes);
+            }
+        }
+
+        self.encode(out);
+
+        Ok(())
+    }
+
+    /// Check the validity rules that the encoding does not enforce: fee
+    /// market transactions can't tip more than their max fee
+    pub fn validate(&self) -> Result<(), TransactionError> {
+        let fees = match self {
+            Self::Eip1559(txn) => Some((txn.max_fee_per_gas, txn.max_priority_fee_per_gas)),
+            Self::Eip4844(txn) => Some((txn.max_fee_per_gas, txn.max_priority_fee_per_gas)),
+            Self::Eip7702(txn) => Some((txn.max_fee_per_gas, txn.max_priority_fee_per_gas)),
+            _ => None,
+        };
+
+        if let Some((max_fee_per_gas, max_priority_fee_per_gas)) = fees {
+            if max_fee_per_gas < max_priority_fee_per_gas {
+                return Err(TransactionError::InvalidFeeMarket {
+                    max_fee_per_gas,
+                    max_priority_fee_per_gas,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encode the transaction as an item of the transactions list of a block
+    /// body, where typed transactions are wrapped in a byte string.
+    ///
+    /// Trie leaves are not wrapped, they are the [Self::encode] form.
+    pub fn encode_for_body(&self, out: &mut dyn BufMut) {
+        if let Self::Legacy(txn) = self {
+            return txn.encode(out);
+        }
+
+        let mut typed = Vec::<u8>::new();
+        self.encode(&mut typed);
+        typed.as_slice().encode(out);
+    }
+
+    /// Encode the transaction as in devp2p `Transactions` messages: typed
+    /// transactions wrapped in a byte string, legacy ones bare. It is the
+    /// same form as [Self::encode_for_body]
+    pub fn encode_enveloped(&self, out: &mut dyn BufMut) {
+        self.encode_for_body(out);
+    }
+
+    /// Decode a transaction from its EIP-2718 encoding.
+    ///
+    /// The receipt is not part of the encoding and is left empty.
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let first = *buf.first().ok_or(alloy_rlp::Error::InputTooShort)?;
+
+        // Legacy transactions are bare RLP lists
+        if first >= alloy_rlp::EMPTY_LIST_CODE {
+            return Ok(Self::Legacy(TxLegacy::decode(buf)?));
+        }
+
+        *buf = &buf[1..];
+        match first {
+            Tx2930::TX_TYPE => Ok(Self::Eip2930(Tx2930::decode(buf)?)),
+            Tx1559::TX_TYPE => Ok(Self::Eip1559(Tx1559::decode(buf)?)),
+            Tx4844::TX_TYPE => Ok(Self::Eip4844(Tx4844::decode(buf)?)),
+            Tx7702::TX_TYPE => Ok(Self::Eip7702(Tx7702::decode(buf)?)),
+            #[cfg(feature = "optimism")]
+            DEPOSIT_TX_TYPE => Ok(Self::Deposit(TxDeposit::decode(buf)?)),
+            #[cfg(feature = "arbitrum")]
+            ARBITRUM_RETRY_TX_TYPE => Ok(Self::ArbitrumRetry(TxArbitrumRetry::decode(buf)?)),
+            tx_type => Err(DecodeError::UnknownTransactionType(tx_type)),
+        }
+    }
+
+    /// Decode a transaction as [Self::decode], failing with
+    /// [DecodeError::TrailingBytes] if bytes follow it. Light clients should
+    /// use this mode
+    pub fn decode_strict(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (txn, rest) = Self::decode_lenient(bytes)?;
+        ensure_no_trailing_bytes(rest)?;
+
+        Ok(txn)
+    }
+
+    /// Decode a transaction as [Self::decode], returning the bytes that
+    /// follow it
+    pub fn decode_lenient(bytes: &[u8]) -> Result<(Self, &[u8]), DecodeError> {
+        let mut buf = bytes;
+        let txn = Self::decode(&mut buf)?;
+
+        Ok((txn, buf))
+    }
+
+    /// Decode a transaction as [Self::decode], handling ambiguous fields as
+    /// `option` says
+    pub fn decode_with(buf: &mut &[u8], option: DecodeOption) -> Result<Self, DecodeError> {
+        let mut txn = Self::decode(buf)?;
+
+        if option == DecodeOption::ZeroAddressAsCreate {
+            let to = match &mut txn {
+                Self::Legacy(txn) => Some(&mut txn.to),
+                Self::Eip2930(txn) => Some(&mut txn.to),
+                Self::Eip1559(txn) => Some(&mut txn.to),
+                _ => None,
+            };
+
+            if let Some(to) = to.filter(|to| **to == TxKind::Call(Address::ZERO)) {
+                *to = TxKind::Create;
+            }
+        }
+
+        Ok(txn)
+    }
+
+    /// Chain id the transaction is valid for. Legacy transactions have one only
+    /// if signed following EIP-155.
+    pub fn chain_id(&self) -> Option<ChainId> {
+        match self {
+            Self::Legacy(txn) => txn.chain_id(),
+            Self::Eip2930(txn) => Some(txn.chain_id),
+            Self::Eip1559(txn) => Some(txn.chain_id),
+            Self::Eip4844(txn) => Some(txn.chain_id),
+            Self::Eip7702(txn) => Some(txn.chain_id),
+            #[cfg(feature = "optimism")]
+            Self::Deposit(_) => None,
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => Some(txn.chain_id),
+        }
+    }
+
+    /// Whether the signature commits to a chain, so that the transaction
+    /// can't be replayed on another one. Legacy transactions are only if
+    /// signed following EIP-155, with `v >= 35`
+    pub fn is_replay_protected(&self) -> bool {
+        match self {
+            Self::Legacy(txn) => txn.signature.v >= U256::from(35),
+            Self::Eip2930(_) | Self::Eip1559(_) | Self::Eip4844(_) | Self::Eip7702(_) => true,
+            // Deposits are not signed, there is no signature to replay
+            #[cfg(feature = "optimism")]
+            Self::Deposit(_) => true,
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(_) => true,
+        }
+    }
+
+    /// Hash of the transaction
+    pub fn hash(&self) -> TxHash {
+        keccak256(self.hash_preimage())
+    }
+
+    /// Bytes hashed by [Self::hash]: the whole encoding, type prefixed.
+    /// Unlike the preimage of [Self::signing_hash], it includes the
+    /// signature
+    pub fn hash_preimage(&self) -> Bytes {
+        let mut buffer = Vec::<u8>::new();
+        self.encode(&mut buffer);
+
+        buffer.into()
+    }
+
+    /// Annotated hex dump of the encoding hashed by [Self::hash]: the type
+    /// byte, the list header and each field, with their offset and length
+    pub fn debug_encoding(&self) -> String {
+        let mut buffer = Vec::<u8>::new();
+        self.encode(&mut buffer);
+
+        let fields: &[&str] = match self {
+            Self::Legacy(_) => &[
+                "nonce",
+                "gas_price",
+                "gas_limit",
+                "to",
+                "value",
+                "data",
+                "v",
+                "r",
+                "s",
+            ],
+            Self::Eip2930(_) => &[
+                "chain_id",
+                "nonce",
+                "gas_price",
+                "gas_limit",
+                "to",
+                "value",
+                "data",
+                "access_list",
+                "v",
+                "r",
+                "s",
+            ],
+            Self::Eip1559(_) => &[
+                "chain_id",
+                "nonce",
+                "max_priority_fee_per_gas",
+                "max_fee_per_gas",
+                "gas_limit",
+                "to",
+                "value",
+                "data",
+                "access_list",
+                "v",
+                "r",
+                "s",
+            ],
+            Self::Eip4844(_) => &[
+                "chain_id",
+                "nonce",
+                "max_priority_fee_per_gas",
+                "max_fee_per_gas",
+                "gas_limit",
+                "to",
+                "value",
+                "data",
+                "access_list",
+                "max_fee_per_blob_gas",
+                "blob_versioned_hashes",
+                "v",
+                "r",
+                "s",
+            ],
+            Self::Eip7702(_) => &[
+                "chain_id",
+                "nonce",
+                "max_priority_fee_per_gas",
+                "max_fee_per_gas",
+                "gas_limit",
+                "to",
+                "value",
+                "data",
+                "access_list",
+                "authorization_list",
+                "v",
+                "r",
+                "s",
+            ],
+            #[cfg(feature = "optimism")]
+            Self::Deposit(_) => &[
+                "source_hash",
+                "from",
+                "to",
+                "mint",
+                "value",
+                "gas_limit",
+                "is_system_transaction",
+                "data",
+            ],
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(_) => &[
+                "chain_id",
+                "nonce",
+                "from",
+                "gas_fee_cap",
+                "gas_limit",
+                "to",
+                "value",
+                "data",
+                "ticket_id",
+                "refund_to",
+                "max_refund",
+                "submission_fee_refund",
+            ],
+        };
+
+        let line = |offset: usize, bytes: &[u8], label: &str| {
+            format!(
+                "{offset:>5} {:>5}  {label}: {}\n",
+                bytes.len(),
+                alloy_primitives::hex::encode(bytes)
+            )
+        };
+
+        let mut dump = String::new();
+        let mut offset = 0;
+        if buffer[0] < alloy_rlp::EMPTY_LIST_CODE {
+            dump += &line(0, &buffer[..1], "type");
+            offset = 1;
+        }
+
+        // Fields are items of the list, each with its own header
+        let mut items = vec![("list header", true)];
+        items.extend(fields.iter().map(|field| (*field, false)));
+        for (label, is_list_header) in items {
+            let mut rest = &buffer[offset..];
+            let Ok(header) = alloy_rlp::Header::decode(&mut rest) else {
+                break;
+            };
+
+            // A single byte is its own header, nothing is consumed for it
+            let mut len = buffer.len() - offset - rest.len();
+            if !is_list_header {
+                len += header.payload_length;
+            }
+
+            dump += &line(offset, &buffer[offset..offset + len], label);
+            offset += len;
+        }
+
+        dump
+    }
+
+    /// Hash signed by the sender of the transaction. Deposits and retries are
+    /// not signed, their hash is returned
+    pub fn signing_hash(&self) -> B256 {
+        match self {
+            Self::Legacy(txn) => txn.signing_hash(),
+            Self::Eip2930(txn) => txn.signing_hash(),
+            Self::Eip1559(txn) => txn.signing_hash(),
+            Self::Eip4844(txn) => txn.signing_hash(),
+            Self::Eip7702(txn) => txn.signing_hash(),
+            #[cfg(feature = "optimism")]
+            Self::Deposit(_) => self.hash(),
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(_) => self.hash(),
+        }
+    }
+
+    /// Recover the sender from the signature. `None` if the signature is invalid
+    pub fn recover_signer(&self) -> Option<Address> {
+        let signature = match self {
+            Self::Legacy(txn) => &txn.signature,
+            Self::Eip2930(txn) => &txn.signature,
+            Self::Eip1559(txn) => &txn.signature,
+            Self::Eip4844(txn) => &txn.signature,
+            Self::Eip7702(txn) => &txn.signature,
+            #[cfg(feature = "optimism")]
+            Self::Deposit(txn) => return Some(txn.from),
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => return Some(txn.from),
+        };
+
+        signature.recover_address(&self.signing_hash())
+    }
+
+    /// Address of the contract deployed by the transaction, derived from the
+    /// sender and its nonce. `None` for calls or if the sender can't be recovered
+    pub fn created_address(&self) -> Option<Address> {
+        let nonce = match self {
+            Self::Legacy(TxLegacy {
+                to: TxKind::Create,
+                nonce,
+                ..
+            })
+            | Self::Eip2930(Tx2930 {
+                to: TxKind::Create,
+                nonce,
+                ..
+            })
+            | Self::Eip1559(Tx1559 {
+                to: TxKind::Create,
+                nonce,
+                ..
+            }) => *nonce,
+            // The nonce of the sender is only known from the receipt
+            #[cfg(feature = "optimism")]
+            Self::Deposit(TxDeposit {
+                to: TxKind::Create,
+                receipt,
+                ..
+            }) => receipt.deposit_nonce?,
+            _ => return None,
+        };
+
+        Some(self.recover_signer()?.create(nonce))
+    }
+
+    /// Recipient of the transaction, `None` for creations
+    pub fn to(&self) -> Option<Address> {
+        let to = match self {
+            Self::Legacy(txn) => txn.to,
+            Self::Eip2930(txn) => txn.to,
+            Self::Eip1559(txn) => txn.to,
+            Self::Eip4844(txn) => return Some(txn.to),
+            Self::Eip7702(txn) => return Some(txn.to),
+            #[cfg(feature = "optimism")]
+            Self::Deposit(txn) => txn.to,
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => txn.to,
+        };
+
+        to.to()
+    }
+
+    /// Nonce of the sender. Deposits have none, it is the one of their
+    /// receipt from Regolith, and 0 before
+    pub fn nonce(&self) -> u64 {
+        match self {
+            Self::Legacy(txn) => txn.nonce,
+            Self::Eip2930(txn) => txn.nonce,
+            Self::Eip1559(txn) => txn.nonce,
+            Self::Eip4844(txn) => txn.nonce,
+            Self::Eip7702(txn) => txn.nonce,
+            #[cfg(feature = "optimism")]
+            Self::Deposit(txn) => txn.receipt.deposit_nonce.unwrap_or_default(),
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => txn.nonce,
+        }
+    }
+
+    /// Gas the transaction may use
+    pub fn gas_limit(&self) -> u64 {
+        match self {
+            Self::Legacy(txn) => txn.gas_limit,
+            Self::Eip2930(txn) => txn.gas_limit,
+            Self::Eip1559(txn) => txn.gas_limit,
+            Self::Eip4844(txn) => txn.gas_limit,
+            Self::Eip7702(txn) => txn.gas_limit,
+            #[cfg(feature = "optimism")]
+            Self::Deposit(txn) => txn.gas_limit,
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => txn.gas_limit,
+        }
+    }
+
+    /// Wei sent with the transaction
+    pub fn value(&self) -> U256 {
+        match self {
+            Self::Legacy(txn) => txn.value,
+            Self::Eip2930(txn) => txn.value,
+            Self::Eip1559(txn) => txn.value,
+            Self::Eip4844(txn) => txn.value,
+            Self::Eip7702(txn) => txn.value,
+            #[cfg(feature = "optimism")]
+            Self::Deposit(txn) => txn.value,
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => txn.value,
+        }
+    }
+
+    /// Calldata of the transaction, or the initcode of a creation
+    pub fn data(&self) -> &Bytes {
+        match self {
+            Self::Legacy(txn) => &txn.data,
+            Self::Eip2930(txn) => &txn.data,
+            Self::Eip1559(txn) => &txn.data,
+            Self::Eip4844(txn) => &txn.data,
+            Self::Eip7702(txn) => &txn.data,
+            #[cfg(feature = "optimism")]
+            Self::Deposit(txn) => &txn.data,
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => &txn.data,
+        }
+    }
+
+    /// Function selector of the call, the first 4 bytes of the calldata.
+    /// `None` when the calldata is shorter, as for plain transfers
+    pub fn selector(&self) -> Option<Selector> {
+        self.data().get(..4).map(Selector::from_slice)
+    }
+
+    /// Number of zero and non zero bytes of the calldata, which are charged
+    /// differently
+    pub fn calldata_byte_stats(&self) -> (usize, usize) {
+        let data = self.data();
+        let zero_bytes = data.iter().filter(|byte| **byte == 0).count();
+
+        (zero_bytes, data.len() - zero_bytes)
+    }
+
+    /// Whether the transaction only sends value to an address: no calldata
+    /// and a non zero value
+    pub fn is_transfer(&self) -> bool {
+        self.to().is_some() && self.data().is_empty() && !self.value().is_zero()
+    }
+
+    /// Recipient of a call or address of the created contract
+    pub fn target_address(&self) -> Option<Address> {
+        self.to().or_else(|| self.created_address())
+    }
+
+    /// Price paid per unit of gas in a block with `base_fee`. Fee market
+    /// transactions pay the base fee plus their tip, up to `max_fee_per_gas`
+    pub fn effective_gas_price(&self, base_fee: u128) -> u128 {
+        let (max_fee_per_gas, max_priority_fee_per_gas) = match self {
+            Self::Legacy(txn) => return txn.gas_price,
+            Self::Eip2930(txn) => return txn.gas_price,
+            Self::Eip1559(txn) => (txn.max_fee_per_gas, txn.max_priority_fee_per_gas),
+            Self::Eip4844(txn) => (txn.max_fee_per_gas, txn.max_priority_fee_per_gas),
+            Self::Eip7702(txn) => (txn.max_fee_per_gas, txn.max_priority_fee_per_gas),
+            // Gas of deposits is bought on L1
+            #[cfg(feature = "optimism")]
+            Self::Deposit(_) => return 0,
+            // Retries pay the base fee only
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => return txn.gas_fee_cap.min(base_fee),
+        };
+
+        max_fee_per_gas.min(base_fee.saturating_add(max_priority_fee_per_gas))
+    }
+
+    /// Wei debited from the sender when the transaction uses `gas_used` in a
+    /// block with `base_fee`: the value and the gas, ignoring refunds.
+    ///
+    /// The gas used by a transaction is not in its receipt, see
+    /// [VerifiedBlock::per_transaction_gas](crate::block::VerifiedBlock::per_transaction_gas).
+    /// The blob fee of EIP-4844 transactions is not included.
+    pub fn sender_debit(&self, base_fee: u128, gas_used: U256) -> U256 {
+        self.value() + U256::from(self.effective_gas_price(base_fee)) * gas_used
+    }
+
+    /// Gas charged before execution: the base cost, calldata, access list,
+    /// initcode (EIP-3860) and authorizations (EIP-7702).
+    ///
+    /// Authorizations are charged [PER_EMPTY_ACCOUNT_COST] each. The part
+    /// refunded when the authority already exists is only known during
+    /// execution, it is not deducted here.
+    pub fn intrinsic_gas(&self) -> u64 {
+        let (to, data, access_list, authorizations) = match self {
+            Self::Legacy(txn) => (txn.to, &txn.data, &[][..], 0),
+            Self::Eip2930(txn) => (txn.to, &txn.data, &txn.access_list[..], 0),
+            Self::Eip1559(txn) => (txn.to, &txn.data, &txn.access_list[..], 0),
+            Self::Eip4844(txn) => (TxKind::Call(txn.to), &txn.data, &txn.access_list[..], 0),
+            Self::Eip7702(txn) => (
+                TxKind::Call(txn.to),
+                &txn.data,
+                &txn.access_list[..],
+                txn.authorization_list.len(),
+            ),
+            #[cfg(feature = "optimism")]
+            Self::Deposit(txn) => (txn.to, &txn.data, &[][..], 0),
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => (txn.to, &txn.data, &[][..], 0),
+        };
+
+        let (zero_bytes, non_zero_bytes) = self.calldata_byte_stats();
+
+        let mut gas = TX_GAS;
+        gas += zero_bytes as u64 * TX_DATA_ZERO_GAS + non_zero_bytes as u64 * TX_DATA_NON_ZERO_GAS;
+        if to == TxKind::Create {
+            gas += TX_CREATE_GAS + (data.len() as u64).div_ceil(32) * INITCODE_WORD_GAS;
+        }
+        for item in access_list {
+            gas += ACCESS_LIST_ADDRESS_GAS;
+            gas += item.storage_key.len() as u64 * ACCESS_LIST_STORAGE_KEY_GAS;
+        }
+        gas += authorizations as u64 * PER_EMPTY_ACCOUNT_COST;
+
+        gas
+    }
+
+    /// EIP-2718 type of the transaction, `None` for legacy transactions
+    pub fn transaction_type(&self) -> Option<u8> {
+        match self {
+            Self::Legacy(_) => None,
+            Self::Eip2930(_) => Some(Tx2930::TX_TYPE),
+            Self::Eip1559(_) => Some(Tx1559::TX_TYPE),
+            Self::Eip4844(_) => Some(Tx4844::TX_TYPE),
+            Self::Eip7702(_) => Some(Tx7702::TX_TYPE),
+            #[cfg(feature = "optimism")]
+            Self::Deposit(_) => Some(DEPOSIT_TX_TYPE),
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(_) => Some(ARBITRUM_RETRY_TX_TYPE),
+        }
+    }
+
+    /// Build the receipt of the transaction, typed as the transaction, with
+    /// the bloom of `logs`
+    pub fn build_receipt(
+        &self,
+        status: bool,
+        cumulative_gas: U256,
+        logs: Vec<Log>,
+    ) -> VerifiedReceipt {
+        let transaction_type = self.transaction_type();
+
+        let mut logs_bloom = Bloom::ZERO;
+        for log in &logs {
+            logs_bloom.accrue_log(log);
+        }
+
+        VerifiedReceipt {
+            transaction_type,
+            status,
+            cumulative_gas_used: cumulative_gas,
+            logs,
+            logs_bloom,
+            #[cfg(feature = "optimism")]
+            deposit_nonce: None,
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: None,
         }
     }
 
@@ -124,8 +963,134 @@ impl VerifiedTransaction {
             Self::Legacy(txn) => &txn.receipt,
             Self::Eip1559(txn) => &txn.receipt,
             Self::Eip2930(txn) => &txn.receipt,
+            Self::Eip4844(txn) => &txn.receipt,
+            Self::Eip7702(txn) => &txn.receipt,
+            #[cfg(feature = "optimism")]
+            Self::Deposit(txn) => &txn.receipt,
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => &txn.receipt,
         }
     }
+
+    fn receipt_mut(&mut self) -> &mut VerifiedReceipt {
+        match self {
+            Self::Legacy(txn) => &mut txn.receipt,
+            Self::Eip1559(txn) => &mut txn.receipt,
+            Self::Eip2930(txn) => &mut txn.receipt,
+            Self::Eip4844(txn) => &mut txn.receipt,
+            Self::Eip7702(txn) => &mut txn.receipt,
+            #[cfg(feature = "optimism")]
+            Self::Deposit(txn) => &mut txn.receipt,
+            #[cfg(feature = "arbitrum")]
+            Self::ArbitrumRetry(txn) => &mut txn.receipt,
+        }
+    }
+
+    /// Whether the transaction executed successfully
+    pub fn succeeded(&self) -> bool {
+        self.receipt().is_success()
+    }
+
+    /// Whether the transaction reverted. It is still included in the block
+    /// and pays for its gas.
+    pub fn reverted(&self) -> bool {
+        !self.succeeded()
+    }
+}
+
+/// Base gas of every transaction
+pub const TX_GAS: u64 = 21000;
+
+/// Additional gas of a contract creation
+pub const TX_CREATE_GAS: u64 = 32000;
+
+/// Gas per zero byte of calldata
+pub const TX_DATA_ZERO_GAS: u64 = 4;
+
+/// Gas per non zero byte of calldata (EIP-2028)
+pub const TX_DATA_NON_ZERO_GAS: u64 = 16;
+
+/// Gas per address of an access list (EIP-2930)
+pub const ACCESS_LIST_ADDRESS_GAS: u64 = 2400;
+
+/// Gas per storage key of an access list (EIP-2930)
+pub const ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1900;
+
+/// Gas per 32 bytes word of initcode (EIP-3860)
+pub const INITCODE_WORD_GAS: u64 = 2;
+
+/// Gas charged per authorization of a [Tx7702]
+pub const PER_EMPTY_ACCOUNT_COST: u64 = 25000;
+
+/// Gas an authorization costs when its authority already exists. The
+/// difference with [PER_EMPTY_ACCOUNT_COST] is refunded during execution
+pub const PER_AUTH_BASE_COST: u64 = 12500;
+
+/// Prefix of the preimage of the hash signed by an [Authorization]
+pub const AUTHORIZATION_MAGIC: u8 = 0x05;
+
+/// Options of [VerifiedTransaction::decode_with]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecodeOption {
+    /// Decode fields as they are encoded
+    #[default]
+    Strict,
+    /// Treat a `to` of 20 zero bytes as a contract creation, as some tools
+    /// encode it. The transaction then no longer hashes as the decoded bytes
+    ZeroAddressAsCreate,
+}
+
+/// Convert a fee from the RPC, which may not fit the 128 bits we store it in
+fn fee_to_u128(value: EU256, field: &'static str) -> Result<u128, TransactionError> {
+    u128::try_from(value).map_err(|_| TransactionError::FeeOverflow { field })
+}
+
+/// Value of the RPC field `field`, as looked up and parsed
+fn required<T, E>(value: Option<Result<T, E>>, field: &'static str) -> Result<T, TransactionError> {
+    value
+        .ok_or(TransactionError::MissingField { field })?
+        .map_err(|_| TransactionError::InvalidField { field })
+}
+
+impl Encodable for VerifiedTransaction {
+    fn encode(&self, out: &mut dyn BufMut) {
+        VerifiedTransaction::encode(self, out)
+    }
+}
+
+/// Decode the transactions list of a block body, where typed transactions
+/// are wrapped in a byte string. See [VerifiedTransaction::encode_for_body].
+///
+/// An item starting at `0xc0` or above is a legacy transaction, any other
+/// is a byte string which must start with an EIP-2718 type in
+/// `0x01..=0x7f`: a legacy transaction can't be wrapped.
+pub fn decode_block_transactions(bytes: &[u8]) -> Result<Vec<VerifiedTransaction>, DecodeError> {
+    let mut buf = bytes;
+    let mut payload = take_list_payload(&mut buf)?;
+    ensure_consumed(buf)?;
+
+    let mut transactions = Vec::new();
+    while !payload.is_empty() {
+        let txn = if payload[0] >= alloy_rlp::EMPTY_LIST_CODE {
+            VerifiedTransaction::Legacy(TxLegacy::decode(&mut payload)?)
+        } else {
+            let mut typed = alloy_rlp::Header::decode_bytes(&mut payload, false)?;
+            match typed.first() {
+                Some(0x01..=0x7f) => {}
+                Some(&tx_type) => return Err(DecodeError::UnknownTransactionType(tx_type)),
+                None => return Err(alloy_rlp::Error::InputTooShort.into()),
+            }
+
+            let txn = VerifiedTransaction::decode(&mut typed)?;
+            ensure_consumed(typed)?;
+
+            txn
+        };
+
+        transactions.push(txn);
+    }
+
+    Ok(transactions)
 }
 
 #[derive(Debug)]
@@ -133,7 +1098,7 @@ pub struct TxLegacy {
     pub nonce: u64,
     pub gas_price: u128,
     pub gas_limit: u64,
-    pub to: Address,
+    pub to: TxKind,
     pub value: U256,
     pub data: Bytes,
     pub signature: Signature,
@@ -141,6 +1106,22 @@ pub struct TxLegacy {
 }
 
 impl TxLegacy {
+    /// Whether the signature commits to a chain id (EIP-155). Frontier
+    /// transactions have `v` 27 or 28
+    pub fn is_eip155(&self) -> bool {
+        self.chain_id().is_some()
+    }
+
+    /// Chain id derived from `v = chain_id * 2 + 35 + y_parity` (EIP-155)
+    pub fn chain_id(&self) -> Option<ChainId> {
+        let v = self.signature.v;
+        if v < U256::from(35) {
+            return None;
+        }
+
+        u64::try_from((v - U256::from(35)) / U256::from(2)).ok()
+    }
+
     fn payload_length(&self) -> usize {
         let mut len = self.nonce.length();
         len += self.gas_price.length();
@@ -172,16 +1153,74 @@ impl TxLegacy {
         self.data.0.encode(out);
         self.signature.encode(out);
     }
-}
 
-#[derive(Debug)]
-pub struct Tx2930 {
-    pub tx_type: u8,
-    pub chain_id: ChainId,
+    fn signing_payload_length(&self) -> usize {
+        let mut len = self.nonce.length();
+        len += self.gas_price.length();
+        len += self.gas_limit.length();
+        len += self.to.length();
+        len += self.value.length();
+        len += self.data.length();
+        if let Some(chain_id) = self.chain_id() {
+            len += chain_id.length();
+            len += 0u8.length() * 2;
+        }
+
+        len
+    }
+
+    /// Hash signed by the sender. EIP-155 transactions commit to the chain id,
+    /// Frontier ones sign only the first six fields
+    pub fn signing_hash(&self) -> B256 {
+        let mut out = Vec::<u8>::new();
+        let header = alloy_rlp::Header {
+            list: true,
+            payload_length: self.signing_payload_length(),
+        };
+
+        header.encode(&mut out);
+
+        self.nonce.encode(&mut out);
+        self.gas_price.encode(&mut out);
+        self.gas_limit.encode(&mut out);
+        self.to.encode(&mut out);
+        self.value.encode(&mut out);
+        self.data.0.encode(&mut out);
+        if let Some(chain_id) = self.chain_id() {
+            chain_id.encode(&mut out);
+            0u8.encode(&mut out);
+            0u8.encode(&mut out);
+        }
+
+        keccak256(out)
+    }
+
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let mut payload = take_list_payload(buf)?;
+
+        let txn = Self {
+            nonce: Decodable::decode(&mut payload)?,
+            gas_price: Decodable::decode(&mut payload)?,
+            gas_limit: Decodable::decode(&mut payload)?,
+            to: Decodable::decode(&mut payload)?,
+            value: Decodable::decode(&mut payload)?,
+            data: Decodable::decode(&mut payload)?,
+            signature: Signature::decode(&mut payload)?,
+            receipt: VerifiedReceipt::default(),
+        };
+        ensure_consumed(payload)?;
+
+        Ok(txn)
+    }
+}
+
+#[derive(Debug)]
+pub struct Tx2930 {
+    pub chain_id: ChainId,
     pub nonce: u64,
     pub gas_price: u128,
     pub gas_limit: u64,
-    pub to: Address,
+    pub to: TxKind,
     pub value: U256,
     pub data: Bytes,
     pub signature: Signature,
@@ -190,6 +1229,9 @@ pub struct Tx2930 {
 }
 
 impl Tx2930 {
+    /// Type byte prefixing the encoding of the transaction
+    pub const TX_TYPE: u8 = 1;
+
     fn payload_length(&self) -> usize {
         let mut len = self.chain_id.length();
         len += self.nonce.length();
@@ -199,7 +1241,9 @@ impl Tx2930 {
         len += self.value.length();
         len += self.data.length();
         len += self.access_list.length();
-        len += self.signature.length();
+        len += self.signature.v.length();
+        len += self.signature.r.length();
+        len += self.signature.s.length();
 
         len
     }
@@ -211,7 +1255,7 @@ impl Tx2930 {
             payload_length,
         };
 
-        out.put_u8(self.tx_type);
+        out.put_u8(Self::TX_TYPE);
         header.encode(out);
 
         self.chain_id.encode(out);
@@ -224,15 +1268,71 @@ impl Tx2930 {
         self.access_list.encode(out);
         self.signature.encode(out);
     }
+
+    fn signing_payload_length(&self) -> usize {
+        let mut len = self.chain_id.length();
+        len += self.nonce.length();
+        len += self.gas_price.length();
+        len += self.gas_limit.length();
+        len += self.to.length();
+        len += self.value.length();
+        len += self.data.length();
+        len += self.access_list.length();
+
+        len
+    }
+
+    /// Hash signed by the sender: `keccak256(tx_type || rlp(fields))`
+    pub fn signing_hash(&self) -> B256 {
+        let mut out = Vec::<u8>::new();
+        let header = alloy_rlp::Header {
+            list: true,
+            payload_length: self.signing_payload_length(),
+        };
+
+        out.put_u8(Self::TX_TYPE);
+        header.encode(&mut out);
+
+        self.chain_id.encode(&mut out);
+        self.nonce.encode(&mut out);
+        self.gas_price.encode(&mut out);
+        self.gas_limit.encode(&mut out);
+        self.to.encode(&mut out);
+        self.value.encode(&mut out);
+        self.data.0.encode(&mut out);
+        self.access_list.encode(&mut out);
+
+        keccak256(out)
+    }
+
+    /// Decode the transaction payload, following the type byte
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let mut payload = take_list_payload(buf)?;
+
+        let txn = Self {
+            chain_id: Decodable::decode(&mut payload)?,
+            nonce: Decodable::decode(&mut payload)?,
+            gas_price: Decodable::decode(&mut payload)?,
+            gas_limit: Decodable::decode(&mut payload)?,
+            to: Decodable::decode(&mut payload)?,
+            value: Decodable::decode(&mut payload)?,
+            data: Decodable::decode(&mut payload)?,
+            access_list: Decodable::decode(&mut payload)?,
+            signature: Signature::decode(&mut payload)?,
+            receipt: VerifiedReceipt::default(),
+        };
+        ensure_consumed(payload)?;
+
+        Ok(txn)
+    }
 }
 
 #[derive(Debug)]
 pub struct Tx1559 {
-    pub tx_type: u8,
     pub chain_id: ChainId,
     pub nonce: u64,
     pub gas_limit: u64,
-    pub to: Address,
+    pub to: TxKind,
     pub value: U256,
     pub data: Bytes,
     pub signature: Signature,
@@ -243,6 +1343,9 @@ pub struct Tx1559 {
 }
 
 impl Tx1559 {
+    /// Type byte prefixing the encoding of the transaction
+    pub const TX_TYPE: u8 = 2;
+
     fn payload_length(&self) -> usize {
         let mut len = self.chain_id.length();
         len += self.nonce.length();
@@ -267,7 +1370,7 @@ impl Tx1559 {
             payload_length,
         };
 
-        out.put_u8(self.tx_type);
+        out.put_u8(Self::TX_TYPE);
         header.encode(out);
 
         self.chain_id.encode(out);
@@ -281,141 +1384,1308 @@ impl Tx1559 {
         self.access_list.encode(out);
         self.signature.encode(out);
     }
-}
 
-#[derive(Debug, RlpDecodable, RlpEncodable)]
-pub struct Signature {
-    pub v: U256,
-    pub r: U256,
-    pub s: U256,
-}
+    fn signing_payload_length(&self) -> usize {
+        let mut len = self.chain_id.length();
+        len += self.nonce.length();
+        len += self.max_priority_fee_per_gas.length();
+        len += self.max_fee_per_gas.length();
+        len += self.gas_limit.length();
+        len += self.to.length();
+        len += self.value.length();
+        len += self.data.length();
+        len += self.access_list.length();
 
-impl Signature {
-    pub fn encode(&self, out: &mut dyn BufMut) {
-        self.v.encode(out);
-        self.r.encode(out);
-        self.s.encode(out);
+        len
+    }
+
+    /// Hash signed by the sender: `keccak256(tx_type || rlp(fields))`
+    pub fn signing_hash(&self) -> B256 {
+        let mut out = Vec::<u8>::new();
+        let header = alloy_rlp::Header {
+            list: true,
+            payload_length: self.signing_payload_length(),
+        };
+
+        out.put_u8(Self::TX_TYPE);
+        header.encode(&mut out);
+
+        self.chain_id.encode(&mut out);
+        self.nonce.encode(&mut out);
+        self.max_priority_fee_per_gas.encode(&mut out);
+        self.max_fee_per_gas.encode(&mut out);
+        self.gas_limit.encode(&mut out);
+        self.to.encode(&mut out);
+        self.value.encode(&mut out);
+        self.data.0.encode(&mut out);
+        self.access_list.encode(&mut out);
+
+        keccak256(out)
+    }
+
+    /// Decode the transaction payload, following the type byte
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let mut payload = take_list_payload(buf)?;
+
+        let txn = Self {
+            chain_id: Decodable::decode(&mut payload)?,
+            nonce: Decodable::decode(&mut payload)?,
+            max_priority_fee_per_gas: Decodable::decode(&mut payload)?,
+            max_fee_per_gas: Decodable::decode(&mut payload)?,
+            gas_limit: Decodable::decode(&mut payload)?,
+            to: Decodable::decode(&mut payload)?,
+            value: Decodable::decode(&mut payload)?,
+            data: Decodable::decode(&mut payload)?,
+            access_list: Decodable::decode(&mut payload)?,
+            signature: Signature::decode(&mut payload)?,
+            receipt: VerifiedReceipt::default(),
+        };
+        ensure_consumed(payload)?;
+
+        Ok(txn)
     }
 }
 
-#[derive(Debug, RlpDecodable, RlpEncodable)]
-pub struct AccessListItem {
-    pub address: Address,
-    pub storage_key: Vec<B256>,
+/// Size in bytes of a blob
+pub const BYTES_PER_BLOB: usize = 131072;
+
+/// Blob gas consumed by each blob
+pub const GAS_PER_BLOB: u64 = 131072;
+
+/// Version byte prepended to the hash of a KZG commitment
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// A blob carried by a [Tx4844] sidecar
+pub type Blob = FixedBytes<BYTES_PER_BLOB>;
+
+/// A KZG commitment or proof
+pub type B48 = FixedBytes<48>;
+
+#[derive(Debug)]
+pub struct Tx4844 {
+    pub chain_id: ChainId,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub signature: Signature,
+    pub access_list: Vec<AccessListItem>,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub max_fee_per_blob_gas: u128,
+    pub blob_versioned_hashes: Vec<B256>,
+    pub receipt: VerifiedReceipt,
 }
 
-#[cfg(test)]
-mod tests {
-    use std::str::FromStr;
+impl Tx4844 {
+    /// Type byte prefixing the encoding of the transaction
+    pub const TX_TYPE: u8 = 3;
 
-    use alloy_primitives::{address, keccak256, uint, BlockHash};
+    fn payload_length(&self) -> usize {
+        let mut len = self.chain_id.length();
+        len += self.nonce.length();
+        len += self.max_priority_fee_per_gas.length();
+        len += self.max_fee_per_gas.length();
+        len += self.gas_limit.length();
+        len += self.to.length();
+        len += self.value.length();
+        len += self.data.length();
+        len += self.access_list.length();
+        len += self.max_fee_per_blob_gas.length();
+        len += self.blob_versioned_hashes.length();
+        len += self.signature.v.length();
+        len += self.signature.r.length();
+        len += self.signature.s.length();
 
-    use super::*;
+        len
+    }
 
-    #[test]
-    fn should_legacy_hash_correctly() {
-        let txn = TxLegacy {
-            nonce: 1752,
-            gas_price: 300000000000,
-            gas_limit: 90277,
-            to: address!("1643E812aE58766192Cf7D2Cf9567dF2C37e9B7F"),
-            value: uint!(3000000000000000000_U256),
-            data: "0xa1903eab0000000000000000000000000000000000000000000000000000000000000000"
-                .parse()
-                .unwrap(),
-            signature: Signature {
-                v: uint!(45_U256),
-                r: "0xb1df344bc5f8d4508b03bc24e73b8a411e6662152fc083bc044e59826cae3421"
-                    .parse()
-                    .unwrap(),
-                s: "0x08d15757b321670c81ad46e61eaa7c58279559af972d048648cfc40ba8ff4133"
-                    .parse()
-                    .unwrap(),
-            },
-            receipt: VerifiedReceipt::default(),
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        let payload_length = self.payload_length();
+        let header = alloy_rlp::Header {
+            list: true,
+            payload_length,
         };
 
-        let mut buffer = Vec::<u8>::new();
-        txn.encode(&mut buffer);
+        out.put_u8(Self::TX_TYPE);
+        header.encode(out);
 
-        assert_eq!(
-            keccak256(buffer),
-            BlockHash::from_str(
-                "0x2dd5d1a058f69df4c374081e0d6be639c65f8b39967d4ea8dc62ec77b4cca1d5"
-            )
-            .unwrap()
-        );
+        self.chain_id.encode(out);
+        self.nonce.encode(out);
+        self.max_priority_fee_per_gas.encode(out);
+        self.max_fee_per_gas.encode(out);
+        self.gas_limit.encode(out);
+        self.to.encode(out);
+        self.value.encode(out);
+        self.data.0.encode(out);
+        self.access_list.encode(out);
+        self.max_fee_per_blob_gas.encode(out);
+        self.blob_versioned_hashes.encode(out);
+        self.signature.encode(out);
     }
 
-    #[test]
-    fn should_type1_hash_correctly() {
-        let txn = Tx2930 {
-            tx_type: 1,
-            chain_id: 1,
-            nonce: 160466,
-            gas_limit: 230684,
-            gas_price: 41014545799,
-            to: address!("A69babEF1cA67A37Ffaf7a485DfFF3382056e78C"),
-            value: uint!(11846912_U256),
-            data: "0x78e111f60000000000000000000000002d876e69e7017421b77822b1bb4c8da1307a19700000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000014470aa0dfe000000000000000000000000e45b4a84e0ad24b8617a489d743c52b84b7acebe0000000000000000000000005b7533812759b45c2b44c19e320ba2cd2681b542000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000002c6b50bca00000000000000000000000000000000000000000000000000006c72001c8d6e00000000000000000000000000000000000000000000000001a5ce878dc1dc50000000000000000000000000000000000000000000013633fa3aece210000000000000000000000000000000000000000000000000013633fa3aece2100000000000000000000000000000000000000000000000000000000000000065673bffff0000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000"
-                .parse()
-                .unwrap(),
-            signature: Signature {
-                v: uint!(1_U256),
-                r: "0xbed4a3918a4478c26dc5cec677fe21dc3599f2597e2b8ff0320c141a1d5213c8"
-                    .parse()
-                    .unwrap(),
-                s: "0x55a57e4e2904c8268c698357e1c77789af9d484122ace41bb69add4f3bc697c0"
-                    .parse()
-                    .unwrap(),
-            },
-            access_list: Vec::new(),
-            receipt: VerifiedReceipt::default()
+    fn signing_payload_length(&self) -> usize {
+        let mut len = self.chain_id.length();
+        len += self.nonce.length();
+        len += self.max_priority_fee_per_gas.length();
+        len += self.max_fee_per_gas.length();
+        len += self.gas_limit.length();
+        len += self.to.length();
+        len += self.value.length();
+        len += self.data.length();
+        len += self.access_list.length();
+        len += self.max_fee_per_blob_gas.length();
+        len += self.blob_versioned_hashes.length();
+
+        len
+    }
+
+    /// Hash signed by the sender: `keccak256(tx_type || rlp(fields))`
+    pub fn signing_hash(&self) -> B256 {
+        let mut out = Vec::<u8>::new();
+        let header = alloy_rlp::Header {
+            list: true,
+            payload_length: self.signing_payload_length(),
         };
 
-        let mut buffer = Vec::<u8>::new();
-        txn.encode(&mut buffer);
+        out.put_u8(Self::TX_TYPE);
+        header.encode(&mut out);
 
-        assert_eq!(
-            keccak256(buffer),
-            BlockHash::from_str(
-                "0x6fa053fe85c3bbda94b727f7a085196222bd80429325b49481b518865ff0fe9f"
-            )
-            .unwrap()
-        );
+        self.chain_id.encode(&mut out);
+        self.nonce.encode(&mut out);
+        self.max_priority_fee_per_gas.encode(&mut out);
+        self.max_fee_per_gas.encode(&mut out);
+        self.gas_limit.encode(&mut out);
+        self.to.encode(&mut out);
+        self.value.encode(&mut out);
+        self.data.0.encode(&mut out);
+        self.access_list.encode(&mut out);
+        self.max_fee_per_blob_gas.encode(&mut out);
+        self.blob_versioned_hashes.encode(&mut out);
+
+        keccak256(out)
     }
 
-    #[test]
-    fn should_type2_hash_correctly() {
-        let txn = Tx1559 {
-            tx_type: 2,
-            chain_id: 1,
-            nonce: 160466,
-            gas_limit: 230684,
-            to: address!("A69babEF1cA67A37Ffaf7a485DfFF3382056e78C"),
-            value: uint!(11846912_U256),
-            data: "0x78e111f60000000000000000000000002d876e69e7017421b77822b1bb4c8da1307a19700000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000014470aa0dfe000000000000000000000000e45b4a84e0ad24b8617a489d743c52b84b7acebe0000000000000000000000005b7533812759b45c2b44c19e320ba2cd2681b542000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000002c6b50bca00000000000000000000000000000000000000000000000000006c72001c8d6e00000000000000000000000000000000000000000000000001a5ce878dc1dc50000000000000000000000000000000000000000000013633fa3aece210000000000000000000000000000000000000000000000000013633fa3aece2100000000000000000000000000000000000000000000000000000000000000065673bffff0000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000"
-                .parse()
-                .unwrap(),
-            signature: Signature {
-                v: uint!(1_U256),
-                r: "0xbed4a3918a4478c26dc5cec677fe21dc3599f2597e2b8ff0320c141a1d5213c8"
-                    .parse()
-                    .unwrap(),
-                s: "0x55a57e4e2904c8268c698357e1c77789af9d484122ace41bb69add4f3bc697c0"
-                    .parse()
-                    .unwrap(),
-            },
-            access_list: Vec::new(),
-            max_fee_per_gas: 61521818698,
-            max_priority_fee_per_gas: 0,
-            receipt: VerifiedReceipt::default()
+    /// Decode the transaction payload, following the type byte
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let mut payload = take_list_payload(buf)?;
+
+        let txn = Self {
+            chain_id: Decodable::decode(&mut payload)?,
+            nonce: Decodable::decode(&mut payload)?,
+            max_priority_fee_per_gas: Decodable::decode(&mut payload)?,
+            max_fee_per_gas: Decodable::decode(&mut payload)?,
+            gas_limit: Decodable::decode(&mut payload)?,
+            to: Decodable::decode(&mut payload)?,
+            value: Decodable::decode(&mut payload)?,
+            data: Decodable::decode(&mut payload)?,
+            access_list: Decodable::decode(&mut payload)?,
+            max_fee_per_blob_gas: Decodable::decode(&mut payload)?,
+            blob_versioned_hashes: Decodable::decode(&mut payload)?,
+            signature: Signature::decode(&mut payload)?,
+            receipt: VerifiedReceipt::default(),
         };
+        ensure_consumed(payload)?;
 
-        let mut buffer = Vec::<u8>::new();
-        txn.encode(&mut buffer);
+        Ok(txn)
+    }
 
-        assert_eq!(
+    /// Check that every versioned hash commits to the matching KZG commitment
+    /// of the sidecar.
+    ///
+    /// A versioned hash is `VERSIONED_HASH_VERSION_KZG || sha256(commitment)[1..]`
+    pub fn verify_blob_versioned_hashes(&self, sidecar: &BlobSidecar) -> bool {
+        if self.blob_versioned_hashes.len() != sidecar.commitments.len() {
+            return false;
+        }
+
+        std::iter::zip(
+            self.blob_versioned_hashes.iter(),
+            sidecar.commitments.iter(),
+        )
+        .all(|(hash, commitment)| hash == &kzg_to_versioned_hash(commitment))
+    }
+}
+
+/// Blobs, commitments and proofs attached to a [Tx4844] in its network
+/// (`PooledTransactions`) form. They are not part of the transaction hash.
+#[derive(Debug, Default)]
+pub struct BlobSidecar {
+    pub blobs: Vec<Blob>,
+    pub commitments: Vec<B48>,
+    pub proofs: Vec<B48>,
+}
+
+/// Compute the versioned hash of a KZG commitment
+pub fn kzg_to_versioned_hash(commitment: &B48) -> B256 {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+
+    B256::new(hash)
+}
+
+/// Transaction setting the code of EOAs to a delegation to a contract
+/// (EIP-7702). It can't create contracts
+#[derive(Debug)]
+pub struct Tx7702 {
+    pub chain_id: ChainId,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub signature: Signature,
+    pub access_list: Vec<AccessListItem>,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub authorization_list: Vec<Authorization>,
+    pub receipt: VerifiedReceipt,
+}
+
+impl Tx7702 {
+    /// Type byte prefixing the encoding of the transaction
+    pub const TX_TYPE: u8 = 4;
+
+    fn payload_length(&self) -> usize {
+        let mut len = self.chain_id.length();
+        len += self.nonce.length();
+        len += self.max_priority_fee_per_gas.length();
+        len += self.max_fee_per_gas.length();
+        len += self.gas_limit.length();
+        len += self.to.length();
+        len += self.value.length();
+        len += self.data.length();
+        len += self.access_list.length();
+        len += self.authorization_list.length();
+        len += self.signature.v.length();
+        len += self.signature.r.length();
+        len += self.signature.s.length();
+
+        len
+    }
+
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        let payload_length = self.payload_length();
+        let header = alloy_rlp::Header {
+            list: true,
+            payload_length,
+        };
+
+        out.put_u8(Self::TX_TYPE);
+        header.encode(out);
+
+        self.chain_id.encode(out);
+        self.nonce.encode(out);
+        self.max_priority_fee_per_gas.encode(out);
+        self.max_fee_per_gas.encode(out);
+        self.gas_limit.encode(out);
+        self.to.encode(out);
+        self.value.encode(out);
+        self.data.0.encode(out);
+        self.access_list.encode(out);
+        self.authorization_list.encode(out);
+        self.signature.encode(out);
+    }
+
+    fn signing_payload_length(&self) -> usize {
+        let mut len = self.chain_id.length();
+        len += self.nonce.length();
+        len += self.max_priority_fee_per_gas.length();
+        len += self.max_fee_per_gas.length();
+        len += self.gas_limit.length();
+        len += self.to.length();
+        len += self.value.length();
+        len += self.data.length();
+        len += self.access_list.length();
+        len += self.authorization_list.length();
+
+        len
+    }
+
+    /// Hash signed by the sender: `keccak256(tx_type || rlp(fields))`
+    pub fn signing_hash(&self) -> B256 {
+        let mut out = Vec::<u8>::new();
+        let header = alloy_rlp::Header {
+            list: true,
+            payload_length: self.signing_payload_length(),
+        };
+
+        out.put_u8(Self::TX_TYPE);
+        header.encode(&mut out);
+
+        self.chain_id.encode(&mut out);
+        self.nonce.encode(&mut out);
+        self.max_priority_fee_per_gas.encode(&mut out);
+        self.max_fee_per_gas.encode(&mut out);
+        self.gas_limit.encode(&mut out);
+        self.to.encode(&mut out);
+        self.value.encode(&mut out);
+        self.data.0.encode(&mut out);
+        self.access_list.encode(&mut out);
+        self.authorization_list.encode(&mut out);
+
+        keccak256(out)
+    }
+
+    /// Accounts delegating their code, with the address they delegate to.
+    /// Authorizations whose signer can't be recovered are skipped, as they
+    /// are during execution
+    pub fn delegations(&self) -> Vec<(Address, Address)> {
+        self.authorization_list
+            .iter()
+            .filter_map(|authorization| {
+                let authority = authorization.recover_authority()?;
+                Some((authority, authorization.address))
+            })
+            .collect()
+    }
+
+    /// Decode the transaction payload, following the type byte
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let mut payload = take_list_payload(buf)?;
+
+        let txn = Self {
+            chain_id: Decodable::decode(&mut payload)?,
+            nonce: Decodable::decode(&mut payload)?,
+            max_priority_fee_per_gas: Decodable::decode(&mut payload)?,
+            max_fee_per_gas: Decodable::decode(&mut payload)?,
+            gas_limit: Decodable::decode(&mut payload)?,
+            to: Decodable::decode(&mut payload)?,
+            value: Decodable::decode(&mut payload)?,
+            data: Decodable::decode(&mut payload)?,
+            access_list: Decodable::decode(&mut payload)?,
+            authorization_list: Decodable::decode(&mut payload)?,
+            signature: Signature::decode(&mut payload)?,
+            receipt: VerifiedReceipt::default(),
+        };
+        ensure_consumed(payload)?;
+
+        Ok(txn)
+    }
+}
+
+/// Type byte of Optimism deposit transactions
+#[cfg(feature = "optimism")]
+pub const DEPOSIT_TX_TYPE: u8 = 0x7e;
+
+#[cfg(feature = "optimism")]
+const DEPOSIT_TX_TYPE_U64: u64 = DEPOSIT_TX_TYPE as u64;
+
+/// Optimism deposit transaction, submitted on L1. It is not signed: `from`
+/// is set by the bridge
+#[cfg(feature = "optimism")]
+#[derive(Debug)]
+pub struct TxDeposit {
+    pub source_hash: B256,
+    pub from: Address,
+    pub to: TxKind,
+    pub mint: u128,
+    pub value: U256,
+    pub gas_limit: u64,
+    pub is_system_transaction: bool,
+    pub data: Bytes,
+    pub receipt: VerifiedReceipt,
+}
+
+#[cfg(feature = "optimism")]
+impl TxDeposit {
+    fn payload_length(&self) -> usize {
+        let mut len = self.source_hash.length();
+        len += self.from.length();
+        len += self.to.length();
+        len += self.mint.length();
+        len += self.value.length();
+        len += self.gas_limit.length();
+        len += self.is_system_transaction.length();
+        len += self.data.length();
+
+        len
+    }
+
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        let payload_length = self.payload_length();
+        let header = alloy_rlp::Header {
+            list: true,
+            payload_length,
+        };
+
+        out.put_u8(DEPOSIT_TX_TYPE);
+        header.encode(out);
+
+        self.source_hash.encode(out);
+        self.from.encode(out);
+        self.to.encode(out);
+        self.mint.encode(out);
+        self.value.encode(out);
+        self.gas_limit.encode(out);
+        self.is_system_transaction.encode(out);
+        self.data.0.encode(out);
+    }
+
+    /// Decode the transaction payload, following the type byte
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let mut payload = take_list_payload(buf)?;
+
+        let txn = Self {
+            source_hash: Decodable::decode(&mut payload)?,
+            from: Decodable::decode(&mut payload)?,
+            to: Decodable::decode(&mut payload)?,
+            mint: Decodable::decode(&mut payload)?,
+            value: Decodable::decode(&mut payload)?,
+            gas_limit: Decodable::decode(&mut payload)?,
+            is_system_transaction: Decodable::decode(&mut payload)?,
+            data: Decodable::decode(&mut payload)?,
+            receipt: VerifiedReceipt::default(),
+        };
+        ensure_consumed(payload)?;
+
+        Ok(txn)
+    }
+}
+
+/// Type byte of Arbitrum retryable ticket redeems (`ArbitrumRetryTx`)
+#[cfg(feature = "arbitrum")]
+pub const ARBITRUM_RETRY_TX_TYPE: u8 = 0x68;
+
+#[cfg(feature = "arbitrum")]
+const ARBITRUM_RETRY_TX_TYPE_U64: u64 = ARBITRUM_RETRY_TX_TYPE as u64;
+
+/// Arbitrum redeem of a retryable ticket, created by the chain and not signed.
+///
+/// Fields follow `ArbitrumRetryTx` of Nitro. Other Arbitrum types, as
+/// `ArbitrumSubmitRetryableTx` (0x69), are not supported.
+#[cfg(feature = "arbitrum")]
+#[derive(Debug)]
+pub struct TxArbitrumRetry {
+    pub chain_id: ChainId,
+    pub nonce: u64,
+    pub from: Address,
+    pub gas_fee_cap: u128,
+    pub gas_limit: u64,
+    pub to: TxKind,
+    pub value: U256,
+    pub data: Bytes,
+    pub ticket_id: B256,
+    pub refund_to: Address,
+    pub max_refund: U256,
+    pub submission_fee_refund: U256,
+    pub receipt: VerifiedReceipt,
+}
+
+#[cfg(feature = "arbitrum")]
+impl TxArbitrumRetry {
+    fn payload_length(&self) -> usize {
+        let mut len = self.chain_id.length();
+        len += self.nonce.length();
+        len += self.from.length();
+        len += self.gas_fee_cap.length();
+        len += self.gas_limit.length();
+        len += self.to.length();
+        len += self.value.length();
+        len += self.data.length();
+        len += self.ticket_id.length();
+        len += self.refund_to.length();
+        len += self.max_refund.length();
+        len += self.submission_fee_refund.length();
+
+        len
+    }
+
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        let payload_length = self.payload_length();
+        let header = alloy_rlp::Header {
+            list: true,
+            payload_length,
+        };
+
+        out.put_u8(ARBITRUM_RETRY_TX_TYPE);
+        header.encode(out);
+
+        self.chain_id.encode(out);
+        self.nonce.encode(out);
+        self.from.encode(out);
+        self.gas_fee_cap.encode(out);
+        self.gas_limit.encode(out);
+        self.to.encode(out);
+        self.value.encode(out);
+        self.data.0.encode(out);
+        self.ticket_id.encode(out);
+        self.refund_to.encode(out);
+        self.max_refund.encode(out);
+        self.submission_fee_refund.encode(out);
+    }
+
+    /// Decode the transaction payload, following the type byte
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        let mut payload = take_list_payload(buf)?;
+
+        let txn = Self {
+            chain_id: Decodable::decode(&mut payload)?,
+            nonce: Decodable::decode(&mut payload)?,
+            from: Decodable::decode(&mut payload)?,
+            gas_fee_cap: Decodable::decode(&mut payload)?,
+            gas_limit: Decodable::decode(&mut payload)?,
+            to: Decodable::decode(&mut payload)?,
+            value: Decodable::decode(&mut payload)?,
+            data: Decodable::decode(&mut payload)?,
+            ticket_id: Decodable::decode(&mut payload)?,
+            refund_to: Decodable::decode(&mut payload)?,
+            max_refund: Decodable::decode(&mut payload)?,
+            submission_fee_refund: Decodable::decode(&mut payload)?,
+            receipt: VerifiedReceipt::default(),
+        };
+        ensure_consumed(payload)?;
+
+        Ok(txn)
+    }
+}
+
+#[derive(Debug, RlpDecodable, RlpEncodable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Signature {
+    pub v: U256,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl Signature {
+    pub fn encode(&self, out: &mut dyn BufMut) {
+        self.v.encode(out);
+        self.r.encode(out);
+        self.s.encode(out);
+    }
+
+    /// Recover the address that signed `prehash`. `v` may be a y parity, 27/28
+    /// or an EIP-155 value
+    pub fn recover_address(&self, prehash: &B256) -> Option<Address> {
+        // An EIP-155 `v` may not fit 64 bits for large chain ids, only its
+        // parity matters: `v = chain_id * 2 + 35 + y_parity`
+        let v = match u64::try_from(self.v) {
+            Ok(v) => v,
+            Err(_) => 35 + u64::from(!self.v.bit(0)),
+        };
+        let signature = alloy_primitives::Signature::from_rs_and_parity(self.r, self.s, v).ok()?;
+
+        signature.recover_address_from_prehash(prehash).ok()
+    }
+
+    /// Y parity of the signature, whichever convention `v` follows
+    fn y_parity(&self) -> bool {
+        match u64::try_from(self.v) {
+            Ok(v @ (0 | 1)) => v == 1,
+            Ok(v @ (27 | 28)) => v == 28,
+            // EIP-155: `v = chain_id * 2 + 35 + y_parity`
+            _ => !self.v.bit(0),
+        }
+    }
+
+    /// Compact `r || s || v` form, as taken by the `ecrecover` precompile and
+    /// most signing libraries. `v` is 27/28 if `legacy_v`, 0/1 otherwise
+    pub fn to_bytes(&self, legacy_v: bool) -> [u8; 65] {
+        let mut bytes = [0; 65];
+        bytes[..32].copy_from_slice(&self.r.to_be_bytes::<32>());
+        bytes[32..64].copy_from_slice(&self.s.to_be_bytes::<32>());
+        bytes[64] = u8::from(self.y_parity()) + if legacy_v { 27 } else { 0 };
+
+        bytes
+    }
+
+    /// Parse the compact `r || s || v` form, where `v` may be 0/1 or 27/28.
+    /// `v` is kept as the y parity, as typed transactions carry it. `None`
+    /// for any other `v`.
+    pub fn from_bytes(bytes: &[u8; 65]) -> Option<Self> {
+        let v = match bytes[64] {
+            v @ (0 | 1) => v,
+            v @ (27 | 28) => v - 27,
+            _ => return None,
+        };
+
+        Some(Self {
+            v: U256::from(v),
+            r: U256::from_be_slice(&bytes[..32]),
+            s: U256::from_be_slice(&bytes[32..64]),
+        })
+    }
+
+    /// Decode `v`, `r` and `s` as they appear inside a transaction
+    pub fn decode(buf: &mut &[u8]) -> Result<Self, DecodeError> {
+        Ok(Self {
+            v: Decodable::decode(buf)?,
+            r: Decodable::decode(buf)?,
+            s: Decodable::decode(buf)?,
+        })
+    }
+}
+
+/// Signature fields as returned by the RPC, where typed transactions may
+/// carry `yParity` next to or in place of `v`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RpcSignature {
+    v: Option<U256>,
+    #[serde(rename = "yParity")]
+    y_parity: Option<U256>,
+    r: U256,
+    s: U256,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Signature {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let signature = RpcSignature::deserialize(deserializer)?;
+        let v = signature
+            .v
+            .or(signature.y_parity)
+            .ok_or_else(|| serde::de::Error::missing_field("v"))?;
+
+        Ok(Self {
+            v,
+            r: signature.r,
+            s: signature.s,
+        })
+    }
+}
+
+#[derive(Debug, RlpDecodable, RlpEncodable)]
+pub struct AccessListItem {
+    pub address: Address,
+    pub storage_key: Vec<B256>,
+}
+
+/// Authorization of a [Tx7702], signed by the authority delegating its code
+/// to `address`
+#[derive(Debug, RlpDecodable, RlpEncodable)]
+pub struct Authorization {
+    pub chain_id: U256,
+    pub address: Address,
+    pub nonce: u64,
+    pub y_parity: u8,
+    pub r: U256,
+    pub s: U256,
+}
+
+impl Authorization {
+    /// Hash signed by the authority: `keccak256(0x05 || rlp([chain_id,
+    /// address, nonce]))`
+    pub fn signing_hash(&self) -> B256 {
+        let mut out = Vec::<u8>::new();
+        let header = alloy_rlp::Header {
+            list: true,
+            payload_length: self.chain_id.length() + self.address.length() + self.nonce.length(),
+        };
+
+        out.put_u8(AUTHORIZATION_MAGIC);
+        header.encode(&mut out);
+
+        self.chain_id.encode(&mut out);
+        self.address.encode(&mut out);
+        self.nonce.encode(&mut out);
+
+        keccak256(out)
+    }
+
+    /// Recover the authority, the account delegating its code
+    pub fn recover_authority(&self) -> Option<Address> {
+        let signature = Signature {
+            v: U256::from(self.y_parity),
+            r: self.r,
+            s: self.s,
+        };
+
+        signature.recover_address(&self.signing_hash())
+    }
+
+    /// Build an authorization from its RPC fields, hex strings. Older nodes
+    /// return the y parity as `v`
+    fn from_rpc(fields: &HashMap<String, String>) -> Result<Self, TransactionError> {
+        let field = |name: &'static str| {
+            fields
+                .get(name)
+                .map(String::as_str)
+                .ok_or(TransactionError::MissingField { field: name })
+        };
+        let (y_parity_field, y_parity) = match fields.get("yParity") {
+            Some(y_parity) => ("yParity", y_parity.as_str()),
+            None => ("v", field("v")?),
+        };
+
+        fn invalid<E>(field: &'static str) -> impl FnOnce(E) -> TransactionError {
+            move |_| TransactionError::InvalidField { field }
+        }
+
+        Ok(Self {
+            chain_id: field("chainId")?.parse().map_err(invalid("chainId"))?,
+            address: field("address")?.parse().map_err(invalid("address"))?,
+            nonce: u64::from_str_radix(field("nonce")?.trim_start_matches("0x"), 16)
+                .map_err(invalid("nonce"))?,
+            y_parity: u8::from_str_radix(y_parity.trim_start_matches("0x"), 16)
+                .map_err(invalid(y_parity_field))?,
+            r: field("r")?.parse().map_err(invalid("r"))?,
+            s: field("s")?.parse().map_err(invalid("s"))?,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::str::FromStr;
+
+    use alloy_primitives::{address, b256, fixed_bytes, uint, BlockHash};
+    use proptest::{
+        collection::vec,
+        prelude::*,
+        strategy::{BoxedStrategy, Union},
+    };
+
+    use super::*;
+    use crate::error::ReceiptError;
+
+    pub(crate) fn any_address() -> impl Strategy<Value = Address> {
+        any::<[u8; 20]>().prop_map(Address::from)
+    }
+
+    pub(crate) fn any_b256() -> impl Strategy<Value = B256> {
+        any::<[u8; 32]>().prop_map(B256::from)
+    }
+
+    pub(crate) fn any_u256() -> impl Strategy<Value = U256> {
+        any::<[u64; 4]>().prop_map(U256::from_limbs)
+    }
+
+    pub(crate) fn any_bytes() -> impl Strategy<Value = Bytes> {
+        vec(any::<u8>(), 0..80).prop_map(Bytes::from)
+    }
+
+    fn any_tx_kind() -> impl Strategy<Value = TxKind> {
+        prop_oneof![Just(TxKind::Create), any_address().prop_map(TxKind::Call)]
+    }
+
+    fn any_signature() -> impl Strategy<Value = Signature> {
+        (any_u256(), any_u256(), any_u256()).prop_map(|(v, r, s)| Signature { v, r, s })
+    }
+
+    fn any_access_list() -> impl Strategy<Value = Vec<AccessListItem>> {
+        vec(
+            (any_address(), vec(any_b256(), 0..3)).prop_map(|(address, storage_key)| {
+                AccessListItem {
+                    address,
+                    storage_key,
+                }
+            }),
+            0..3,
+        )
+    }
+
+    fn any_authorization_list() -> impl Strategy<Value = Vec<Authorization>> {
+        vec(
+            (
+                any_u256(),
+                any_address(),
+                any::<u64>(),
+                any::<u8>(),
+                any_u256(),
+                any_u256(),
+            )
+                .prop_map(|(chain_id, address, nonce, y_parity, r, s)| Authorization {
+                    chain_id,
+                    address,
+                    nonce,
+                    y_parity,
+                    r,
+                    s,
+                }),
+            0..3,
+        )
+    }
+
+    /// Transactions of every supported type, with an empty receipt as after
+    /// decoding
+    fn any_transaction() -> impl Strategy<Value = VerifiedTransaction> {
+        let common = || {
+            (
+                any::<u64>(),
+                any::<u64>(),
+                any_u256(),
+                any_bytes(),
+                any_signature(),
+            )
+        };
+
+        let legacy = (common(), any::<u128>(), any_tx_kind()).prop_map(
+            |((nonce, gas_limit, value, data, signature), gas_price, to)| {
+                VerifiedTransaction::Legacy(TxLegacy {
+                    nonce,
+                    gas_price,
+                    gas_limit,
+                    to,
+                    value,
+                    data,
+                    signature,
+                    receipt: VerifiedReceipt::default(),
+                })
+            },
+        );
+        let eip2930 = (
+            common(),
+            any::<u64>(),
+            any::<u128>(),
+            any_tx_kind(),
+            any_access_list(),
+        )
+            .prop_map(
+                |(
+                    (nonce, gas_limit, value, data, signature),
+                    chain_id,
+                    gas_price,
+                    to,
+                    access_list,
+                )| {
+                    VerifiedTransaction::Eip2930(Tx2930 {
+                        chain_id,
+                        nonce,
+                        gas_price,
+                        gas_limit,
+                        to,
+                        value,
+                        data,
+                        signature,
+                        access_list,
+                        receipt: VerifiedReceipt::default(),
+                    })
+                },
+            );
+        let eip1559 = (
+            common(),
+            any::<u64>(),
+            any::<(u128, u128)>(),
+            any_tx_kind(),
+            any_access_list(),
+        )
+            .prop_map(
+                |(
+                    (nonce, gas_limit, value, data, signature),
+                    chain_id,
+                    (max_fee_per_gas, max_priority_fee_per_gas),
+                    to,
+                    access_list,
+                )| {
+                    VerifiedTransaction::Eip1559(Tx1559 {
+                        chain_id,
+                        nonce,
+                        gas_limit,
+                        to,
+                        value,
+                        data,
+                        signature,
+                        access_list,
+                        max_fee_per_gas,
+                        max_priority_fee_per_gas,
+                        receipt: VerifiedReceipt::default(),
+                    })
+                },
+            );
+        let eip4844 = (
+            common(),
+            any::<u64>(),
+            any::<(u128, u128, u128)>(),
+            any_address(),
+            any_access_list(),
+            vec(any_b256(), 1..4),
+        )
+            .prop_map(
+                |(
+                    (nonce, gas_limit, value, data, signature),
+                    chain_id,
+                    (max_fee_per_gas, max_priority_fee_per_gas, max_fee_per_blob_gas),
+                    to,
+                    access_list,
+                    blob_versioned_hashes,
+                )| {
+                    VerifiedTransaction::Eip4844(Tx4844 {
+                        chain_id,
+                        nonce,
+                        gas_limit,
+                        to,
+                        value,
+                        data,
+                        signature,
+                        access_list,
+                        max_fee_per_gas,
+                        max_priority_fee_per_gas,
+                        max_fee_per_blob_gas,
+                        blob_versioned_hashes,
+                        receipt: VerifiedReceipt::default(),
+                    })
+                },
+            );
+
+        let eip7702 = (
+            common(),
+            any::<u64>(),
+            any::<(u128, u128)>(),
+            any_address(),
+            any_access_list(),
+            any_authorization_list(),
+        )
+            .prop_map(
+                |(
+                    (nonce, gas_limit, value, data, signature),
+                    chain_id,
+                    (max_fee_per_gas, max_priority_fee_per_gas),
+                    to,
+                    access_list,
+                    authorization_list,
+                )| {
+                    VerifiedTransaction::Eip7702(Tx7702 {
+                        chain_id,
+                        nonce,
+                        gas_limit,
+                        to,
+                        value,
+                        data,
+                        signature,
+                        access_list,
+                        max_fee_per_gas,
+                        max_priority_fee_per_gas,
+                        authorization_list,
+                        receipt: VerifiedReceipt::default(),
+                    })
+                },
+            );
+
+        #[allow(unused_mut)]
+        let mut strategies: Vec<BoxedStrategy<VerifiedTransaction>> = vec![
+            legacy.boxed(),
+            eip2930.boxed(),
+            eip1559.boxed(),
+            eip4844.boxed(),
+            eip7702.boxed(),
+        ];
+
+        #[cfg(feature = "optimism")]
+        strategies.push(
+            (
+                (any_b256(), any_address(), any_tx_kind(), any::<u128>()),
+                (any_u256(), any::<u64>(), any::<bool>(), any_bytes()),
+            )
+                .prop_map(
+                    |(
+                        (source_hash, from, to, mint),
+                        (value, gas_limit, is_system_transaction, data),
+                    )| {
+                        VerifiedTransaction::Deposit(TxDeposit {
+                            source_hash,
+                            from,
+                            to,
+                            mint,
+                            value,
+                            gas_limit,
+                            is_system_transaction,
+                            data,
+                            receipt: VerifiedReceipt::default(),
+                        })
+                    },
+                )
+                .boxed(),
+        );
+
+        #[cfg(feature = "arbitrum")]
+        strategies.push(
+            (
+                (any::<u64>(), any::<u64>(), any_address(), any::<u128>()),
+                (any::<u64>(), any_tx_kind(), any_u256(), any_bytes()),
+                (any_b256(), any_address(), any_u256(), any_u256()),
+            )
+                .prop_map(
+                    |(
+                        (chain_id, nonce, from, gas_fee_cap),
+                        (gas_limit, to, value, data),
+                        (ticket_id, refund_to, max_refund, submission_fee_refund),
+                    )| {
+                        VerifiedTransaction::ArbitrumRetry(TxArbitrumRetry {
+                            chain_id,
+                            nonce,
+                            from,
+                            gas_fee_cap,
+                            gas_limit,
+                            to,
+                            value,
+                            data,
+                            ticket_id,
+                            refund_to,
+                            max_refund,
+                            submission_fee_refund,
+                            receipt: VerifiedReceipt::default(),
+                        })
+                    },
+                )
+                .boxed(),
+        );
+
+        Union::new(strategies)
+    }
+
+    proptest! {
+        #[test]
+        fn should_decode_what_is_encoded(txn in any_transaction()) {
+            let mut buffer = Vec::<u8>::new();
+            txn.encode(&mut buffer);
+
+            let mut input = buffer.as_slice();
+            let decoded = VerifiedTransaction::decode(&mut input).unwrap();
+            prop_assert!(input.is_empty());
+
+            let mut encoded = Vec::<u8>::new();
+            decoded.encode(&mut encoded);
+            prop_assert_eq!(encoded, buffer);
+            prop_assert_eq!(decoded.hash(), txn.hash());
+        }
+
+        #[test]
+        fn should_reject_corrupted_encoding_without_panicking(
+            txn in any_transaction(),
+            position in any::<prop::sample::Index>(),
+            byte in any::<u8>(),
+        ) {
+            let mut buffer = Vec::<u8>::new();
+            txn.encode(&mut buffer);
+
+            let position = position.index(buffer.len());
+            let _ = VerifiedTransaction::decode(&mut &buffer[..position]);
+
+            buffer[position] = byte;
+            let _ = VerifiedTransaction::decode(&mut buffer.as_slice());
+        }
+
+        #[test]
+        fn should_decode_arbitrary_bytes_without_panicking(bytes in vec(any::<u8>(), 0..256)) {
+            let _ = VerifiedTransaction::decode(&mut bytes.as_slice());
+            let _ = VerifiedTransaction::decode_with(
+                &mut bytes.as_slice(),
+                DecodeOption::ZeroAddressAsCreate,
+            );
+        }
+    }
+
+    pub(crate) fn legacy_fixture() -> TxLegacy {
+        TxLegacy {
+            nonce: 1752,
+            gas_price: 300000000000,
+            gas_limit: 90277,
+            to: TxKind::Call(address!("1643E812aE58766192Cf7D2Cf9567dF2C37e9B7F")),
+            value: uint!(3000000000000000000_U256),
+            data: "0xa1903eab0000000000000000000000000000000000000000000000000000000000000000"
+                .parse()
+                .unwrap(),
+            signature: Signature {
+                v: uint!(45_U256),
+                r: "0xb1df344bc5f8d4508b03bc24e73b8a411e6662152fc083bc044e59826cae3421"
+                    .parse()
+                    .unwrap(),
+                s: "0x08d15757b321670c81ad46e61eaa7c58279559af972d048648cfc40ba8ff4133"
+                    .parse()
+                    .unwrap(),
+            },
+            receipt: VerifiedReceipt::default(),
+        }
+    }
+
+    pub(crate) fn type1_fixture() -> Tx2930 {
+        Tx2930 {
+            chain_id: 1,
+            nonce: 160466,
+            gas_limit: 230684,
+            gas_price: 41014545799,
+            to: TxKind::Call(address!("A69babEF1cA67A37Ffaf7a485DfFF3382056e78C")),
+            value: uint!(11846912_U256),
+            data: "0x78e111f60000000000000000000000002d876e69e7017421b77822b1bb4c8da1307a19700000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000014470aa0dfe000000000000000000000000e45b4a84e0ad24b8617a489d743c52b84b7acebe0000000000000000000000005b7533812759b45c2b44c19e320ba2cd2681b542000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000002c6b50bca00000000000000000000000000000000000000000000000000006c72001c8d6e00000000000000000000000000000000000000000000000001a5ce878dc1dc50000000000000000000000000000000000000000000013633fa3aece210000000000000000000000000000000000000000000000000013633fa3aece2100000000000000000000000000000000000000000000000000000000000000065673bffff0000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000"
+                .parse()
+                .unwrap(),
+            signature: Signature {
+                v: uint!(1_U256),
+                r: "0xbed4a3918a4478c26dc5cec677fe21dc3599f2597e2b8ff0320c141a1d5213c8"
+                    .parse()
+                    .unwrap(),
+                s: "0x55a57e4e2904c8268c698357e1c77789af9d484122ace41bb69add4f3bc697c0"
+                    .parse()
+                    .unwrap(),
+            },
+            access_list: Vec::new(),
+            receipt: VerifiedReceipt::default(),
+        }
+    }
+
+    pub(crate) fn type2_fixture() -> Tx1559 {
+        Tx1559 {
+            chain_id: 1,
+            nonce: 160466,
+            gas_limit: 230684,
+            to: TxKind::Call(address!("A69babEF1cA67A37Ffaf7a485DfFF3382056e78C")),
+            value: uint!(11846912_U256),
+            data: "0x78e111f60000000000000000000000002d876e69e7017421b77822b1bb4c8da1307a19700000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000014470aa0dfe000000000000000000000000e45b4a84e0ad24b8617a489d743c52b84b7acebe0000000000000000000000005b7533812759b45c2b44c19e320ba2cd2681b542000000000000000000000000c02aaa39b223fe8d0a0e5c4f27ead9083c756cc200000000000000000000000000000000000000000000000000000002c6b50bca00000000000000000000000000000000000000000000000000006c72001c8d6e00000000000000000000000000000000000000000000000001a5ce878dc1dc50000000000000000000000000000000000000000000013633fa3aece210000000000000000000000000000000000000000000000000013633fa3aece2100000000000000000000000000000000000000000000000000000000000000065673bffff0000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000"
+                .parse()
+                .unwrap(),
+            signature: Signature {
+                v: uint!(1_U256),
+                r: "0xbed4a3918a4478c26dc5cec677fe21dc3599f2597e2b8ff0320c141a1d5213c8"
+                    .parse()
+                    .unwrap(),
+                s: "0x55a57e4e2904c8268c698357e1c77789af9d484122ace41bb69add4f3bc697c0"
+                    .parse()
+                    .unwrap(),
+            },
+            access_list: Vec::new(),
+            max_fee_per_gas: 61521818698,
+            max_priority_fee_per_gas: 0,
+            receipt: VerifiedReceipt::default(),
+        }
+    }
+
+    /// A blob transaction carrying the empty blob
+    pub(crate) fn type3_fixture() -> Tx4844 {
+        Tx4844 {
+            chain_id: 1,
+            nonce: 0,
+            gas_limit: 21000,
+            to: address!("A69babEF1cA67A37Ffaf7a485DfFF3382056e78C"),
+            value: U256::ZERO,
+            data: Bytes::new(),
+            signature: Signature {
+                v: U256::ZERO,
+                r: U256::ZERO,
+                s: U256::ZERO,
+            },
+            access_list: Vec::new(),
+            max_fee_per_gas: 0,
+            max_priority_fee_per_gas: 0,
+            max_fee_per_blob_gas: 1,
+            blob_versioned_hashes: vec![b256!(
+                "010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+            )],
+            receipt: VerifiedReceipt::default(),
+        }
+    }
+
+    /// A delegation with one authorization, from a devnet
+    pub(crate) fn type4_fixture() -> Tx7702 {
+        Tx7702 {
+            chain_id: 0x1a5ee289c,
+            nonce: 26,
+            gas_limit: 63660,
+            to: address!("6d2d4e1c2326a069f36f5d6337470dc26adb7156"),
+            value: U256::ZERO,
+            data: Bytes::new(),
+            signature: Signature {
+                v: uint!(1_U256),
+                r: "0xb3bf7d6877864913bba04d6f93d98009a5af16ee9c12295cd634962a2346b67c"
+                    .parse()
+                    .unwrap(),
+                s: "0x31ca4a874afa964ec7643e58c6b56b35b1bcc7698eb1b5e15e61e78b353bd42d"
+                    .parse()
+                    .unwrap(),
+            },
+            access_list: Vec::new(),
+            max_fee_per_gas: 235375008,
+            max_priority_fee_per_gas: 235375000,
+            authorization_list: vec![Authorization {
+                chain_id: uint!(0x1a5ee289c_U256),
+                address: address!("529f773125642b12a44bd543005650989eceaa2a"),
+                nonce: 26,
+                y_parity: 0,
+                r: "0x9b3de20cf8bd07f3c5c55c38c920c146f081bc5ab4580d0c87786b256cdab3c2"
+                    .parse()
+                    .unwrap(),
+                s: "0x074841956f4832bace3c02aed34b8f0a2812450da3728752edbb5b5e1da04497"
+                    .parse()
+                    .unwrap(),
+            }],
+            receipt: VerifiedReceipt::default(),
+        }
+    }
+
+    /// First transaction of mainnet, in block 46147: a transfer without
+    /// calldata
+    pub(crate) fn transfer_fixture() -> TxLegacy {
+        TxLegacy {
+            nonce: 0,
+            gas_price: 50000000000000,
+            gas_limit: 21000,
+            to: TxKind::Call(address!("5df9b87991262f6ba471f09758cde1c0fc1de734")),
+            value: uint!(31337_U256),
+            data: Bytes::new(),
+            signature: Signature {
+                v: uint!(28_U256),
+                r: "0x88ff6cf0fefd94db46111149ae4bfc179e9b94721fffd821d38d16464b3f71d0"
+                    .parse()
+                    .unwrap(),
+                s: "0x45e0aff800961cfce805daef7016b9b675c137a6a41a548f7b60a3484c06a33a"
+                    .parse()
+                    .unwrap(),
+            },
+            receipt: VerifiedReceipt::default(),
+        }
+    }
+
+    /// Key signing the fixtures that need a recoverable sender
+    pub(crate) fn signing_key() -> k256::ecdsa::SigningKey {
+        k256::ecdsa::SigningKey::from_slice(&[0x11; 32]).unwrap()
+    }
+
+    /// Sign `prehash` with [signing_key], returning `r`, `s` and the y parity
+    pub(crate) fn sign(prehash: &B256) -> (U256, U256, u64) {
+        let (signature, recovery_id) = signing_key()
+            .sign_prehash_recoverable(prehash.as_slice())
+            .unwrap();
+        let (r, s) = signature.split_bytes();
+
+        (
+            U256::from_be_slice(&r),
+            U256::from_be_slice(&s),
+            recovery_id.is_y_odd() as u64,
+        )
+    }
+
+    #[test]
+    fn should_legacy_hash_correctly() {
+        let txn = legacy_fixture();
+
+        let mut buffer = Vec::<u8>::new();
+        txn.encode(&mut buffer);
+
+        assert_eq!(
+            keccak256(buffer),
+            BlockHash::from_str(
+                "0x2dd5d1a058f69df4c374081e0d6be639c65f8b39967d4ea8dc62ec77b4cca1d5"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn should_type1_hash_correctly() {
+        let txn = type1_fixture();
+
+        let mut buffer = Vec::<u8>::new();
+        txn.encode(&mut buffer);
+
+        assert_eq!(
+            keccak256(buffer),
+            BlockHash::from_str(
+                "0x59ef7b47284897f1f68f4bd9612c2c388c4a7e5d0e6d9803bfb0d83cdee180c5"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn should_type2_hash_correctly() {
+        let txn = type2_fixture();
+
+        let mut buffer = Vec::<u8>::new();
+        txn.encode(&mut buffer);
+
+        assert_eq!(
             keccak256(buffer),
             BlockHash::from_str(
                 "0xd6792b3f289f49876449d68af4706cc8cbfa5a9b480fdb7e5e0fa1fd79374348"
@@ -423,4 +2693,995 @@ mod tests {
             .unwrap()
         );
     }
+
+    #[test]
+    fn should_recover_delegations() {
+        let txn = type4_fixture();
+        // The sender delegates its own code
+        assert_eq!(
+            txn.delegations(),
+            vec![(
+                address!("6d2d4e1c2326a069f36f5d6337470dc26adb7156"),
+                address!("529f773125642b12a44bd543005650989eceaa2a")
+            )]
+        );
+        assert_eq!(
+            txn.authorization_list[0].signing_hash(),
+            b256!("093bfe1047d30b15e1bded45c6338301e8e77723d24805418d655f810202971a")
+        );
+
+        let mut authorization = Authorization {
+            chain_id: U256::from(1),
+            address: address!("529f773125642b12a44bd543005650989eceaa2a"),
+            nonce: 0,
+            y_parity: 0,
+            r: U256::ZERO,
+            s: U256::ZERO,
+        };
+        assert_eq!(authorization.recover_authority(), None);
+
+        let (r, s, y_parity) = sign(&authorization.signing_hash());
+        authorization.r = r;
+        authorization.s = s;
+        authorization.y_parity = y_parity as u8;
+        let txn = Tx7702 {
+            authorization_list: vec![authorization],
+            ..type4_fixture()
+        };
+        assert_eq!(
+            txn.delegations(),
+            vec![(
+                Address::from_private_key(&signing_key()),
+                address!("529f773125642b12a44bd543005650989eceaa2a")
+            )]
+        );
+    }
+
+    #[test]
+    fn should_type4_hash_correctly() {
+        let txn = VerifiedTransaction::Eip7702(type4_fixture());
+
+        assert_eq!(
+            txn.hash(),
+            b256!("adc3f24d05f05f1065debccb1c4b033eaa35917b69b343d88d9062cdf8ecad83")
+        );
+        assert_eq!(
+            txn.recover_signer(),
+            Some(address!("6d2d4e1c2326a069f36f5d6337470dc26adb7156"))
+        );
+    }
+
+    #[test]
+    fn should_handle_empty_calldata() {
+        let txn = VerifiedTransaction::Legacy(transfer_fixture());
+
+        assert_eq!(txn.selector(), None);
+        assert_eq!(txn.intrinsic_gas(), 21000);
+        assert_eq!(
+            txn.hash(),
+            b256!("5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060")
+        );
+
+        // Empty data is the empty string
+        let dump = txn.debug_encoding();
+        assert_eq!(dump.lines().nth(6), Some("   37     1  data: 80"));
+
+        let call = VerifiedTransaction::Eip1559(type2_fixture());
+        assert_eq!(call.selector(), Some(fixed_bytes!("78e111f6")));
+    }
+
+    #[test]
+    fn should_access_common_fields() {
+        let legacy = VerifiedTransaction::Legacy(legacy_fixture());
+        assert_eq!(legacy.nonce(), 1752);
+        assert_eq!(legacy.gas_limit(), 90277);
+        assert_eq!(
+            legacy.to(),
+            Some(address!("1643E812aE58766192Cf7D2Cf9567dF2C37e9B7F"))
+        );
+        assert_eq!(legacy.value(), uint!(3000000000000000000_U256));
+        assert_eq!(legacy.data(), &legacy_fixture().data);
+
+        for txn in [
+            VerifiedTransaction::Eip2930(type1_fixture()),
+            VerifiedTransaction::Eip1559(type2_fixture()),
+        ] {
+            assert_eq!(txn.nonce(), 160466);
+            assert_eq!(txn.gas_limit(), 230684);
+            assert_eq!(
+                txn.to(),
+                Some(address!("A69babEF1cA67A37Ffaf7a485DfFF3382056e78C"))
+            );
+            assert_eq!(txn.value(), uint!(11846912_U256));
+            assert_eq!(txn.data(), &type2_fixture().data);
+        }
+    }
+
+    #[test]
+    fn should_classify_transfers() {
+        let transfer = VerifiedTransaction::Legacy(transfer_fixture());
+        assert!(transfer.is_transfer());
+
+        let call = VerifiedTransaction::Eip1559(type2_fixture());
+        assert!(!call.is_transfer());
+
+        let create = VerifiedTransaction::Legacy(TxLegacy {
+            to: TxKind::Create,
+            ..transfer_fixture()
+        });
+        assert_eq!(create.to(), None);
+        assert!(!create.is_transfer());
+
+        let no_value = VerifiedTransaction::Legacy(TxLegacy {
+            value: U256::ZERO,
+            ..transfer_fixture()
+        });
+        assert!(!no_value.is_transfer());
+    }
+
+    #[test]
+    fn should_hash_preimage() {
+        let transactions = [
+            VerifiedTransaction::Legacy(legacy_fixture()),
+            VerifiedTransaction::Eip2930(type1_fixture()),
+            VerifiedTransaction::Eip1559(type2_fixture()),
+            VerifiedTransaction::Eip4844(type3_fixture()),
+            VerifiedTransaction::Eip7702(type4_fixture()),
+        ];
+
+        for txn in transactions {
+            let preimage = txn.hash_preimage();
+            assert_eq!(keccak256(&preimage), txn.hash());
+        }
+
+        let typed = VerifiedTransaction::Eip1559(type2_fixture()).hash_preimage();
+        assert_eq!(typed[0], 2);
+    }
+
+    #[test]
+    fn should_compute_sender_debit() {
+        let transfer = VerifiedTransaction::Legacy(transfer_fixture());
+        assert_eq!(
+            transfer.sender_debit(0, uint!(21000_U256)),
+            uint!(1_050_000_000_000_031_337_U256)
+        );
+
+        // Without tip, the base fee is paid per gas
+        let call = VerifiedTransaction::Eip1559(type2_fixture());
+        assert_eq!(
+            call.sender_debit(41014545799, uint!(50000_U256)),
+            uint!(11846912_U256) + uint!(41014545799_U256) * uint!(50000_U256)
+        );
+    }
+
+    #[test]
+    fn should_count_calldata_bytes() {
+        // A selector and a zero argument
+        let txn = VerifiedTransaction::Legacy(legacy_fixture());
+        assert_eq!(txn.calldata_byte_stats(), (32, 4));
+
+        let transfer = VerifiedTransaction::Legacy(transfer_fixture());
+        assert_eq!(transfer.calldata_byte_stats(), (0, 0));
+    }
+
+    #[test]
+    fn should_compute_intrinsic_gas() {
+        let transfer = VerifiedTransaction::Eip1559(Tx1559 {
+            data: Bytes::new(),
+            ..type2_fixture()
+        });
+        assert_eq!(transfer.intrinsic_gas(), TX_GAS);
+
+        // 65 zero and 3 non zero bytes, one address with two storage keys
+        let mut txn = type4_fixture();
+        txn.data = Bytes::from([[0u8; 65].as_slice(), &[1, 2, 3]].concat());
+        txn.access_list = vec![AccessListItem {
+            address: Address::ZERO,
+            storage_key: vec![B256::ZERO; 2],
+        }];
+        let authorization = &txn.authorization_list[0];
+        txn.authorization_list.push(Authorization {
+            nonce: authorization.nonce + 1,
+            ..*authorization
+        });
+
+        assert_eq!(
+            VerifiedTransaction::Eip7702(txn).intrinsic_gas(),
+            21000 + 65 * 4 + 3 * 16 + 2400 + 2 * 1900 + 2 * 25000
+        );
+
+        // Creations pay for their initcode too
+        let create = VerifiedTransaction::Legacy(TxLegacy {
+            to: TxKind::Create,
+            data: Bytes::from(vec![1; 33]),
+            ..legacy_fixture()
+        });
+        assert_eq!(create.intrinsic_gas(), 21000 + 32000 + 33 * 16 + 2 * 2);
+    }
+
+    #[test]
+    fn should_hash_with_access_list() {
+        // Ropsten transaction with one address and two storage keys
+        let txn = Tx1559 {
+            chain_id: 3,
+            nonce: 2,
+            gas_limit: 27200,
+            to: TxKind::Call(address!("8210357f377e901f18e45294e86a2a32215cc3c9")),
+            value: uint!(123_U256),
+            data: Bytes::new(),
+            signature: Signature {
+                v: uint!(1_U256),
+                r: "0xf13b5088108f783f4b6048d4be456971118aabfb88be96bb541d734b6c2b20dc"
+                    .parse()
+                    .unwrap(),
+                s: "0x13fb7eb25a7d5df42a176cd4c6a086e19163ed7cd8ffba015f939d24f66bc17a"
+                    .parse()
+                    .unwrap(),
+            },
+            access_list: vec![AccessListItem {
+                address: address!("8ba1f109551bd432803012645ac136ddd64dba72"),
+                storage_key: vec![
+                    B256::ZERO,
+                    b256!("0000000000000000000000000000000000000000000000000000000000000042"),
+                ],
+            }],
+            max_fee_per_gas: 1000000014,
+            max_priority_fee_per_gas: 1000000000,
+            receipt: VerifiedReceipt::default(),
+        };
+
+        let txn = VerifiedTransaction::Eip1559(txn);
+        assert_eq!(
+            txn.hash(),
+            b256!("824384376c5972498c6fcafe71fd8cad1689f64e7d5e270d025a898638c0c34d")
+        );
+        assert_eq!(
+            txn.recover_signer(),
+            Some(address!("541d6a0e9ca9e7a083e41e2e178eef9f22d7492e"))
+        );
+    }
+
+    #[test]
+    fn should_round_trip_access_list_without_storage_keys() {
+        // Three addresses warmed without any slot, encoded as `[address, []]`
+        let raw = alloy_primitives::hex::decode("01f90126018223ff850a02ffee00830f4240940000000000a8fb09af944ab3baf7a9b3e1ab29d880b876200200001525000000000b69ffb300000000557b933a7c2c45672b610f8954a3deb39a51a8cae53ec727dbdeb9e2d5456c3be40cff031ab40a55724d5c9c618a2152e99a45649a3b8cf198321f46720b722f4ec38f99ba3bb1303258d2e816e6a95b25647e01bd0967c1b9599fa3521939871d1d0888f845d694724d5c9c618a2152e99a45649a3b8cf198321f46c0d694720b722f4ec38f99ba3bb1303258d2e816e6a95bc0d69425647e01bd0967c1b9599fa3521939871d1d0888c001a08323efae7b9993bd31a58da7924359d24b5504aa2b33194fcc5ae206e65d2e62a054ce201e3b4b5cd38eb17c56ee2f9111b2e164efcd57b3e70fa308a0a51f7014").unwrap();
+
+        let txn = VerifiedTransaction::decode(&mut raw.as_slice()).unwrap();
+        let VerifiedTransaction::Eip2930(type1) = &txn else {
+            panic!("expected an EIP-2930 transaction");
+        };
+        assert_eq!(type1.access_list.len(), 3);
+        assert!(type1
+            .access_list
+            .iter()
+            .all(|item| item.storage_key.is_empty()));
+        assert_eq!(
+            type1.access_list[0].address,
+            address!("724d5c9c618a2152e99a45649a3b8cf198321f46")
+        );
+
+        let mut encoded = Vec::<u8>::new();
+        txn.encode(&mut encoded);
+        assert_eq!(encoded, raw);
+        assert_eq!(txn.hash(), keccak256(&raw));
+        assert_eq!(
+            txn.recover_signer(),
+            Some(address!("e9c790e8fde820ded558a4771b72eec916c04763"))
+        );
+    }
+
+    #[test]
+    fn should_verify_blob_versioned_hashes() {
+        // Commitment of the empty blob
+        let commitment = fixed_bytes!("c00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000");
+
+        let mut txn = type3_fixture();
+
+        let sidecar = BlobSidecar {
+            blobs: Vec::new(),
+            commitments: vec![commitment],
+            proofs: Vec::new(),
+        };
+
+        assert!(txn.verify_blob_versioned_hashes(&sidecar));
+
+        txn.blob_versioned_hashes[0].0[31] ^= 1;
+        assert!(!txn.verify_blob_versioned_hashes(&sidecar));
+    }
+
+    #[test]
+    fn should_prefix_encoding_with_type() {
+        let typed = [
+            (
+                VerifiedTransaction::Eip2930(type1_fixture()),
+                Tx2930::TX_TYPE,
+            ),
+            (
+                VerifiedTransaction::Eip1559(type2_fixture()),
+                Tx1559::TX_TYPE,
+            ),
+            (
+                VerifiedTransaction::Eip4844(type3_fixture()),
+                Tx4844::TX_TYPE,
+            ),
+            (
+                VerifiedTransaction::Eip7702(type4_fixture()),
+                Tx7702::TX_TYPE,
+            ),
+        ];
+
+        for (expected, (txn, tx_type)) in (1..=4).zip(typed) {
+            let mut buffer = Vec::new();
+            txn.encode(&mut buffer);
+
+            assert_eq!(tx_type, expected);
+            assert_eq!(buffer[0], expected);
+        }
+    }
+
+    #[test]
+    fn should_detect_replay_protection() {
+        assert!(VerifiedTransaction::Legacy(legacy_fixture()).is_replay_protected());
+
+        let mut unprotected = legacy_fixture();
+        unprotected.signature.v = uint!(27_U256);
+        assert!(!VerifiedTransaction::Legacy(unprotected).is_replay_protected());
+
+        assert!(VerifiedTransaction::Eip1559(type2_fixture()).is_replay_protected());
+    }
+
+    #[test]
+    fn should_try_encode_check_invariants() {
+        let mut buffer = Vec::<u8>::new();
+        let txn = VerifiedTransaction::Eip4844(type3_fixture());
+        assert_eq!(txn.try_encode(&mut buffer), Ok(()));
+
+        let mut encoded = Vec::<u8>::new();
+        txn.encode(&mut encoded);
+        assert_eq!(buffer, encoded);
+
+        let mut no_blobs = type3_fixture();
+        no_blobs.blob_versioned_hashes.clear();
+        let mut buffer = Vec::<u8>::new();
+        assert_eq!(
+            VerifiedTransaction::Eip4844(no_blobs).try_encode(&mut buffer),
+            Err(EncodeError::NoBlobHashes)
+        );
+        assert!(buffer.is_empty());
+
+        let mut no_chain_id = type2_fixture();
+        no_chain_id.chain_id = 0;
+        assert_eq!(
+            VerifiedTransaction::Eip1559(no_chain_id).try_encode(&mut buffer),
+            Err(EncodeError::MissingChainId)
+        );
+    }
+
+    #[test]
+    fn should_validate_fee_market() {
+        assert_eq!(
+            VerifiedTransaction::Eip1559(type2_fixture()).validate(),
+            Ok(())
+        );
+        assert_eq!(
+            VerifiedTransaction::Legacy(legacy_fixture()).validate(),
+            Ok(())
+        );
+
+        let mut equal = type3_fixture();
+        equal.max_priority_fee_per_gas = equal.max_fee_per_gas;
+        assert_eq!(VerifiedTransaction::Eip4844(equal).validate(), Ok(()));
+
+        let mut inverted = type2_fixture();
+        inverted.max_fee_per_gas = 1_000_000_000;
+        inverted.max_priority_fee_per_gas = 2_000_000_000;
+        assert_eq!(
+            VerifiedTransaction::Eip1559(inverted).validate(),
+            Err(TransactionError::InvalidFeeMarket {
+                max_fee_per_gas: 1_000_000_000,
+                max_priority_fee_per_gas: 2_000_000_000
+            })
+        );
+    }
+
+    #[test]
+    fn should_build_receipt_typed_as_transaction() {
+        let receipt = crate::receipt::tests::receipt_fixture(None);
+
+        let type2 = VerifiedTransaction::Eip1559(type2_fixture());
+        let built = type2.build_receipt(true, receipt.cumulative_gas_used, receipt.logs.clone());
+        assert_eq!(built.transaction_type, Some(2));
+        assert_eq!(built.logs_bloom, receipt.logs_bloom);
+        assert_eq!(built.cumulative_gas_used, receipt.cumulative_gas_used);
+        assert!(built.status);
+
+        let legacy = VerifiedTransaction::Legacy(legacy_fixture());
+        let built = legacy.build_receipt(false, U256::ZERO, Vec::new());
+        assert_eq!(built.transaction_type, None);
+        assert_eq!(built.logs_bloom, Bloom::ZERO);
+    }
+
+    #[test]
+    fn should_debug_encoding() {
+        let dump = VerifiedTransaction::Legacy(legacy_fixture()).debug_encoding();
+        let lines: Vec<&str> = dump.lines().collect();
+
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[0], "    0     2  list header: f893");
+        assert_eq!(lines[1], "    2     3  nonce: 8206d8");
+        assert_eq!(lines[2], "    5     6  gas_price: 8545d964b800");
+        assert_eq!(lines[3], "   11     4  gas_limit: 830160a5");
+        assert_eq!(lines[7], "   82     1  v: 2d");
+        assert!(lines[9].starts_with("  116    33  s: a008d157"));
+
+        // Typed transactions start with their type
+        let dump = VerifiedTransaction::Eip1559(type2_fixture()).debug_encoding();
+        assert_eq!(dump.lines().next(), Some("    0     1  type: 02"));
+        assert_eq!(dump.lines().count(), 14);
+    }
+
+    #[test]
+    fn should_report_execution_status() {
+        let mut txn = type2_fixture();
+        txn.receipt.status = true;
+        let txn = VerifiedTransaction::Eip1559(txn);
+        assert!(txn.succeeded());
+        assert!(!txn.reverted());
+
+        let mut txn = legacy_fixture();
+        txn.receipt.status = false;
+        let txn = VerifiedTransaction::Legacy(txn);
+        assert!(!txn.succeeded());
+        assert!(txn.reverted());
+    }
+
+    #[test]
+    fn should_detect_legacy_and_typed_body_transactions() {
+        let transactions = [
+            VerifiedTransaction::Eip1559(type2_fixture()),
+            VerifiedTransaction::Legacy(legacy_fixture()),
+            VerifiedTransaction::Eip7702(type4_fixture()),
+        ];
+        let mut items = Vec::<u8>::new();
+        for txn in &transactions {
+            txn.encode_for_body(&mut items);
+        }
+        let body = |items: &[u8]| {
+            let mut body = Vec::<u8>::new();
+            alloy_rlp::Header {
+                list: true,
+                payload_length: items.len(),
+            }
+            .encode(&mut body);
+            body.extend_from_slice(items);
+            body
+        };
+
+        let decoded = decode_block_transactions(&body(&items)).unwrap();
+        assert!(matches!(
+            decoded.as_slice(),
+            [
+                VerifiedTransaction::Eip1559(_),
+                VerifiedTransaction::Legacy(_),
+                VerifiedTransaction::Eip7702(_)
+            ]
+        ));
+        assert_eq!(decoded[1].hash(), transactions[1].hash());
+
+        // A legacy transaction wrapped as a typed one
+        let mut legacy = Vec::<u8>::new();
+        transactions[1].encode(&mut legacy);
+        let mut wrapped = Vec::<u8>::new();
+        legacy.as_slice().encode(&mut wrapped);
+        assert_eq!(
+            decode_block_transactions(&body(&wrapped)).unwrap_err(),
+            DecodeError::UnknownTransactionType(legacy[0])
+        );
+    }
+
+    #[test]
+    fn should_round_trip_block_body_transactions() {
+        let transactions = [
+            VerifiedTransaction::Legacy(legacy_fixture()),
+            VerifiedTransaction::Eip2930(type1_fixture()),
+            VerifiedTransaction::Eip1559(type2_fixture()),
+        ];
+
+        let mut items = Vec::<u8>::new();
+        for txn in &transactions {
+            txn.encode_for_body(&mut items);
+        }
+        let mut body = Vec::<u8>::new();
+        alloy_rlp::Header {
+            list: true,
+            payload_length: items.len(),
+        }
+        .encode(&mut body);
+        body.extend_from_slice(&items);
+
+        // The legacy transaction is a bare list, the typed ones byte strings
+        let mut legacy = Vec::<u8>::new();
+        transactions[0].encode(&mut legacy);
+        assert!(items.starts_with(&legacy));
+        assert_eq!(items[legacy.len()], 0xb9);
+
+        let decoded = decode_block_transactions(&body).unwrap();
+        assert_eq!(decoded.len(), 3);
+
+        let mut encoded = Vec::<u8>::new();
+        for txn in &decoded {
+            txn.encode_for_body(&mut encoded);
+        }
+        assert_eq!(encoded, items);
+        assert_eq!(
+            decoded.iter().map(|txn| txn.hash()).collect::<Vec<_>>(),
+            transactions
+                .iter()
+                .map(|txn| txn.hash())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn should_encode_enveloped() {
+        let txn = VerifiedTransaction::Eip1559(type2_fixture());
+        let mut typed = Vec::<u8>::new();
+        txn.encode(&mut typed);
+
+        let mut enveloped = Vec::<u8>::new();
+        txn.encode_enveloped(&mut enveloped);
+
+        // A string longer than 255 bytes, with a two bytes length
+        assert_eq!(enveloped[0], 0xb9);
+        let mut buf = enveloped.as_slice();
+        let header = alloy_rlp::Header::decode(&mut buf).unwrap();
+        assert!(!header.list);
+        assert_eq!(buf, typed);
+
+        let legacy = VerifiedTransaction::Legacy(legacy_fixture());
+        let mut enveloped = Vec::<u8>::new();
+        legacy.encode_enveloped(&mut enveloped);
+        assert_eq!(enveloped, legacy.hash_preimage());
+    }
+
+    #[test]
+    fn should_decode_round_trip() {
+        let mut buffer = Vec::<u8>::new();
+        type2_fixture().encode(&mut buffer);
+
+        let txn = VerifiedTransaction::decode(&mut buffer.as_slice()).unwrap();
+
+        let mut encoded = Vec::<u8>::new();
+        txn.encode(&mut encoded);
+        assert_eq!(encoded, buffer);
+    }
+
+    #[test]
+    fn should_report_trailing_bytes() {
+        let txn = VerifiedTransaction::Eip1559(type2_fixture());
+        let mut buffer = Vec::<u8>::new();
+        txn.encode(&mut buffer);
+        assert_eq!(
+            VerifiedTransaction::decode_strict(&buffer).map(|txn| txn.hash()),
+            Ok(txn.hash())
+        );
+
+        buffer.extend_from_slice(&[0xde, 0xad, 0x00]);
+        assert_eq!(
+            VerifiedTransaction::decode_strict(&buffer).unwrap_err(),
+            DecodeError::TrailingBytes(3)
+        );
+
+        let (decoded, rest) = VerifiedTransaction::decode_lenient(&buffer).unwrap();
+        assert_eq!(decoded.hash(), txn.hash());
+        assert_eq!(rest, [0xde, 0xad, 0x00]);
+    }
+
+    #[test]
+    fn should_decode_zero_address_as_create_when_asked() {
+        let mut txn = legacy_fixture();
+        txn.to = TxKind::Call(Address::ZERO);
+
+        let mut buffer = Vec::<u8>::new();
+        txn.encode(&mut buffer);
+
+        let strict =
+            VerifiedTransaction::decode_with(&mut buffer.as_slice(), DecodeOption::default())
+                .unwrap();
+        let VerifiedTransaction::Legacy(strict) = strict else {
+            panic!("expected a legacy transaction")
+        };
+        assert_eq!(strict.to, TxKind::Call(Address::ZERO));
+
+        let lenient = VerifiedTransaction::decode_with(
+            &mut buffer.as_slice(),
+            DecodeOption::ZeroAddressAsCreate,
+        )
+        .unwrap();
+        let VerifiedTransaction::Legacy(lenient) = lenient else {
+            panic!("expected a legacy transaction")
+        };
+        assert_eq!(lenient.to, TxKind::Create);
+    }
+
+    #[test]
+    fn should_reject_non_minimal_nonce() {
+        let mut buffer = Vec::<u8>::new();
+        legacy_fixture().encode(&mut buffer);
+
+        // Re-encode the nonce (`0x8206d8`) with a leading zero byte
+        assert_eq!(&buffer[..3], &[0xf8, 0x93, 0x82]);
+        let mut malformed = vec![0xf8, 0x94, 0x83, 0x00];
+        malformed.extend_from_slice(&buffer[3..]);
+
+        assert_eq!(
+            VerifiedTransaction::decode(&mut malformed.as_slice()).unwrap_err(),
+            DecodeError::NonMinimalInteger
+        );
+    }
+
+    #[test]
+    fn should_report_chain_id() {
+        let legacy = VerifiedTransaction::Legacy(legacy_fixture());
+        let type1 = VerifiedTransaction::Eip2930(type1_fixture());
+        let type2 = VerifiedTransaction::Eip1559(type2_fixture());
+
+        assert_eq!(legacy.chain_id(), Some(5));
+        assert_eq!(type1.chain_id(), Some(1));
+        assert_eq!(type2.chain_id(), Some(1));
+
+        let mut pre_eip155 = legacy_fixture();
+        pre_eip155.signature.v = uint!(27_U256);
+        assert_eq!(pre_eip155.chain_id(), None);
+    }
+
+    #[test]
+    fn should_target_address_of_call() {
+        let mut txn = type2_fixture();
+        let (r, s, y_parity) = sign(&txn.signing_hash());
+        txn.signature = Signature {
+            v: U256::from(y_parity),
+            r,
+            s,
+        };
+        let txn = VerifiedTransaction::Eip1559(txn);
+
+        assert_eq!(
+            txn.recover_signer(),
+            Some(Address::from_private_key(&signing_key()))
+        );
+        assert_eq!(txn.created_address(), None);
+        assert_eq!(
+            txn.target_address(),
+            Some(address!("A69babEF1cA67A37Ffaf7a485DfFF3382056e78C"))
+        );
+    }
+
+    #[test]
+    fn should_target_address_of_create() {
+        let mut txn = legacy_fixture();
+        txn.to = TxKind::Create;
+        txn.nonce = 7;
+        // Keep the fixture chain id (5): v = 5 * 2 + 35 + y_parity
+        let (r, s, y_parity) = sign(&txn.signing_hash());
+        txn.signature = Signature {
+            v: U256::from(45 + y_parity),
+            r,
+            s,
+        };
+        let txn = VerifiedTransaction::Legacy(txn);
+
+        let sender = Address::from_private_key(&signing_key());
+        assert_eq!(txn.recover_signer(), Some(sender));
+        assert_eq!(txn.target_address(), Some(sender.create(7)));
+        assert_eq!(txn.target_address(), txn.created_address());
+
+        // A tampered signature recovers someone else
+        let VerifiedTransaction::Legacy(mut txn) = txn else {
+            unreachable!()
+        };
+        txn.nonce = 8;
+        let txn = VerifiedTransaction::Legacy(txn);
+        assert_ne!(txn.recover_signer(), Some(sender));
+    }
+
+    #[test]
+    fn should_recover_sender_of_large_chain_id() {
+        // Sepolia, and a chain id whose `v` doesn't fit 64 bits
+        for chain_id in [11155111, u64::MAX] {
+            let mut txn = legacy_fixture();
+            let v = U256::from(chain_id) * U256::from(2) + U256::from(35);
+            txn.signature.v = v;
+            assert_eq!(txn.chain_id(), Some(chain_id));
+
+            let (r, s, y_parity) = sign(&txn.signing_hash());
+            txn.signature = Signature {
+                v: v + U256::from(y_parity),
+                r,
+                s,
+            };
+            let txn = VerifiedTransaction::Legacy(txn);
+
+            assert_eq!(txn.chain_id(), Some(chain_id));
+            assert_eq!(
+                txn.recover_signer(),
+                Some(Address::from_private_key(&signing_key()))
+            );
+        }
+    }
+
+    #[test]
+    fn should_recover_frontier_sender() {
+        let frontier = transfer_fixture();
+        assert!(!frontier.is_eip155());
+        assert!(legacy_fixture().is_eip155());
+
+        let mut buffer = Vec::<u8>::new();
+        frontier.encode(&mut buffer);
+        assert_eq!(
+            keccak256(&buffer),
+            b256!("5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060")
+        );
+
+        let txn = VerifiedTransaction::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(txn.chain_id(), None);
+        assert_eq!(
+            txn.recover_signer(),
+            Some(address!("a1e4380a3b1f749673e270229993ee55f35663b4"))
+        );
+    }
+
+    #[test]
+    fn should_reject_fee_overflowing_u128() {
+        let mut transaction = ethers::types::Transaction {
+            transaction_type: Some(EU64::zero()),
+            gas_price: Some(EU256::from(u128::MAX) + 1),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt::default();
+
+        assert_eq!(
+            VerifiedTransaction::new(&transaction, &receipt).unwrap_err(),
+            TransactionError::FeeOverflow { field: "gas_price" }
+        );
+
+        transaction.gas_price = Some(EU256::from(u128::MAX));
+        assert!(VerifiedTransaction::new(&transaction, &receipt).is_ok());
+    }
+
+    #[test]
+    fn should_parse_rpc_authorizations() {
+        let fixture = type4_fixture();
+        let mut transaction = ethers::types::Transaction {
+            transaction_type: Some(EU64::from(4)),
+            chain_id: Some(EU256::from(fixture.chain_id)),
+            to: Some(fixture.to.0 .0.into()),
+            max_fee_per_gas: Some(EU256::from(fixture.max_fee_per_gas)),
+            max_priority_fee_per_gas: Some(EU256::from(fixture.max_priority_fee_per_gas)),
+            access_list: Some(Default::default()),
+            ..Default::default()
+        };
+        let authorization = serde_json::json!({
+            "chainId": "0x1a5ee289c",
+            "address": "0x529f773125642b12a44bd543005650989eceaa2a",
+            "nonce": "0x1a",
+            "yParity": "0x0",
+            "r": "0x9b3de20cf8bd07f3c5c55c38c920c146f081bc5ab4580d0c87786b256cdab3c2",
+            "s": "0x74841956f4832bace3c02aed34b8f0a2812450da3728752edbb5b5e1da04497",
+        });
+
+        transaction.other.insert(
+            "authorizationList".to_string(),
+            serde_json::json!([authorization]),
+        );
+        match VerifiedTransaction::new_without_receipt(&transaction).unwrap() {
+            VerifiedTransaction::Eip7702(txn) => assert_eq!(
+                alloy_rlp::encode(&txn.authorization_list),
+                alloy_rlp::encode(&fixture.authorization_list)
+            ),
+            txn => panic!("unexpected transaction {txn:?}"),
+        }
+
+        let mut missing = authorization.clone();
+        missing.as_object_mut().unwrap().remove("r");
+        transaction.other.insert(
+            "authorizationList".to_string(),
+            serde_json::json!([missing]),
+        );
+        assert_eq!(
+            VerifiedTransaction::new_without_receipt(&transaction).unwrap_err(),
+            TransactionError::MissingField { field: "r" }
+        );
+
+        let mut invalid = authorization;
+        invalid["nonce"] = serde_json::json!("0xnonce");
+        transaction.other.insert(
+            "authorizationList".to_string(),
+            serde_json::json!([invalid]),
+        );
+        assert_eq!(
+            VerifiedTransaction::new_without_receipt(&transaction).unwrap_err(),
+            TransactionError::InvalidField { field: "nonce" }
+        );
+
+        transaction.other.remove("authorizationList");
+        assert_eq!(
+            VerifiedTransaction::new_without_receipt(&transaction).unwrap_err(),
+            TransactionError::MissingField {
+                field: "authorizationList"
+            }
+        );
+    }
+
+    #[test]
+    fn should_reject_receipt_with_too_many_topics() {
+        let transaction = ethers::types::Transaction {
+            transaction_type: Some(EU64::zero()),
+            gas_price: Some(EU256::one()),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            logs: vec![ethers::types::Log {
+                topics: vec![EH256::zero(); 5],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            VerifiedTransaction::new(&transaction, &receipt).unwrap_err(),
+            TransactionError::Receipt(ReceiptError::TooManyTopics {
+                log_index: 0,
+                count: 5
+            })
+        );
+    }
+
+    #[cfg(feature = "optimism")]
+    #[test]
+    fn should_deposit_hash_correctly() {
+        // L1 attributes deposit of OP mainnet block 127446283
+        let txn = TxDeposit {
+            source_hash: b256!("04e9a69416471ead93b02f0c279ab11ca0b635db5c1726a56faf22623bafde52"),
+            from: address!("deaddeaddeaddeaddeaddeaddeaddeaddead0001"),
+            to: TxKind::Call(address!("4200000000000000000000000000000000000015")),
+            mint: 0,
+            value: U256::ZERO,
+            gas_limit: 1000000,
+            is_system_transaction: false,
+            data: "0x440a5e200000146b000f79c50000000000000003000000006725333f000000000141e287000000000000000000000000000000000000000000000000000000012439ee7e0000000000000000000000000000000000000000000000000000000063f363e973e96e7145ff001c81b9562cba7b6104eeb12a2bc4ab9f07c27d45cd81a986620000000000000000000000006887246668a3b87f54deb3b94ba47a6f63f32985"
+                .parse()
+                .unwrap(),
+            receipt: VerifiedReceipt::default(),
+        };
+
+        let mut buffer = Vec::<u8>::new();
+        txn.encode(&mut buffer);
+        assert_eq!(
+            keccak256(&buffer),
+            b256!("bc9329afac05556497441e2b3ee4c5d4da7ca0b2a4c212c212d0739e94a24df9")
+        );
+
+        let decoded = VerifiedTransaction::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded.hash(), keccak256(&buffer));
+        assert_eq!(
+            decoded.recover_signer(),
+            Some(address!("deaddeaddeaddeaddeaddeaddeaddeaddead0001"))
+        );
+    }
+
+    #[cfg(feature = "arbitrum")]
+    #[test]
+    fn should_encode_arbitrum_retry() {
+        let txn = TxArbitrumRetry {
+            chain_id: 42161,
+            nonce: 7,
+            from: address!("1111111111111111111111111111111111111111"),
+            gas_fee_cap: 100000000,
+            gas_limit: 500000,
+            to: TxKind::Call(address!("2222222222222222222222222222222222222222")),
+            value: uint!(1000000000000000000_U256),
+            data: "0xdeadbeef".parse().unwrap(),
+            ticket_id: b256!("3333333333333333333333333333333333333333333333333333333333333333"),
+            refund_to: address!("4444444444444444444444444444444444444444"),
+            max_refund: uint!(50000000000000000_U256),
+            submission_fee_refund: uint!(1000000000000000_U256),
+            receipt: VerifiedReceipt::default(),
+        };
+
+        // `0x68 || rlp([chain_id, nonce, from, gas_fee_cap, gas, to, value,
+        // data, ticket_id, refund_to, max_refund, submission_fee_refund])`
+        let expected: Bytes = "0x68f88b82a4b1079411111111111111111111111111111111111111118405f5e1008307a120942222222222222222222222222222222222222222880de0b6b3a764000084deadbeefa0333333333333333333333333333333333333333333333333333333333333333394444444444444444444444444444444444444444487b1a2bc2ec5000087038d7ea4c68000"
+            .parse()
+            .unwrap();
+
+        let mut buffer = Vec::<u8>::new();
+        txn.encode(&mut buffer);
+        assert_eq!(buffer, expected.to_vec());
+
+        let decoded = VerifiedTransaction::decode(&mut buffer.as_slice()).unwrap();
+        assert_eq!(decoded.hash(), keccak256(&expected));
+        assert_eq!(decoded.chain_id(), Some(42161));
+    }
+
+    #[test]
+    fn should_encode_compact_signature() {
+        let signature = legacy_fixture().signature;
+        let compact = signature.to_bytes(true);
+
+        assert_eq!(
+            compact[..32],
+            b256!("b1df344bc5f8d4508b03bc24e73b8a411e6662152fc083bc044e59826cae3421")[..]
+        );
+        assert_eq!(
+            compact[32..64],
+            b256!("08d15757b321670c81ad46e61eaa7c58279559af972d048648cfc40ba8ff4133")[..]
+        );
+        // `v = 45` is EIP-155 on chain 5 with a zero y parity
+        assert_eq!(compact[64], 27);
+        assert_eq!(signature.to_bytes(false)[64], 0);
+
+        let odd = Signature {
+            v: uint!(1_U256),
+            ..signature
+        };
+        assert_eq!(odd.to_bytes(true)[64], 28);
+    }
+
+    #[test]
+    fn should_decode_compact_signature() {
+        let txn = legacy_fixture();
+        let prehash = txn.signing_hash();
+
+        for legacy_v in [true, false] {
+            let compact = txn.signature.to_bytes(legacy_v);
+            let signature = Signature::from_bytes(&compact).unwrap();
+
+            assert_eq!(signature.v, U256::ZERO);
+            assert_eq!(signature.r, txn.signature.r);
+            assert_eq!(signature.s, txn.signature.s);
+            assert_eq!(signature.to_bytes(legacy_v), compact);
+            assert_eq!(
+                signature.recover_address(&prehash),
+                txn.signature.recover_address(&prehash)
+            );
+        }
+
+        let mut compact = txn.signature.to_bytes(true);
+        for v in [2, 26, 29, 37] {
+            compact[64] = v;
+            assert!(Signature::from_bytes(&compact).is_none());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn should_signature_round_trip_rpc_json() {
+        // Fields of `eth_getTransactionByHash` for the legacy fixture
+        let rpc = serde_json::json!({
+            "hash": "0x2dd5d1a058f69df4c374081e0d6be639c65f8b39967d4ea8dc62ec77b4cca1d5",
+            "nonce": "0x6d8",
+            "v": "0x2d",
+            "r": "0xb1df344bc5f8d4508b03bc24e73b8a411e6662152fc083bc044e59826cae3421",
+            "s": "0x8d15757b321670c81ad46e61eaa7c58279559af972d048648cfc40ba8ff4133",
+        });
+
+        let signature: Signature = serde_json::from_value(rpc.clone()).unwrap();
+        assert_eq!(signature.v, legacy_fixture().signature.v);
+        assert_eq!(signature.r, legacy_fixture().signature.r);
+        assert_eq!(signature.s, legacy_fixture().signature.s);
+
+        let serialized = serde_json::to_value(&signature).unwrap();
+        assert_eq!(serialized["v"], rpc["v"]);
+        assert_eq!(serialized["r"], rpc["r"]);
+        assert_eq!(serialized["s"], rpc["s"]);
+
+        let typed: Signature = serde_json::from_value(serde_json::json!({
+            "yParity": "0x1",
+            "r": "0x1",
+            "s": "0x2",
+        }))
+        .unwrap();
+        assert_eq!(typed.v, uint!(1_U256));
+    }
 }
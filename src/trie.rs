@@ -0,0 +1,747 @@
+//! Tries of items keyed by their index, as the transactions and receipts
+//! tries
+use std::collections::{BTreeMap, BTreeSet};
+
+use alloy_primitives::{Bytes, B256};
+use alloy_rlp::{Encodable, EMPTY_STRING_CODE};
+use alloy_trie::{HashBuilder, Nibbles, EMPTY_ROOT_HASH};
+
+use crate::{error::RangeError, hasher::keccak256, utils::index_for_rlp};
+
+/// Trie implementation of the crate: [HashBuilder], or [HasherTrie] with the
+/// `custom-keccak` feature, as [HashBuilder] always hashes with
+/// `alloy_primitives`
+#[cfg(not(feature = "custom-keccak"))]
+pub type DefaultTrie = HashBuilder;
+
+/// Trie implementation of the crate: [HashBuilder], or [HasherTrie] with the
+/// `custom-keccak` feature, as [HashBuilder] always hashes with
+/// `alloy_primitives`
+#[cfg(feature = "custom-keccak")]
+pub type DefaultTrie = HasherTrie;
+
+/// Merkle Patricia trie implementation the tries of items are built with,
+/// [HashBuilder] by default
+pub trait TrieBackend {
+    /// Empty trie, retaining the nodes that prove the keys of `targets`
+    fn with_proof_targets(targets: Vec<Nibbles>) -> Self;
+
+    /// Add a leaf. Keys are inserted in increasing order
+    fn insert_leaf(&mut self, key: Nibbles, value: &[u8]);
+
+    /// Root of the trie of the leaves inserted so far
+    fn root(&mut self) -> B256;
+
+    /// Nodes retained for the proof targets, keyed by their path. Called
+    /// after [Self::root]
+    fn proof(&mut self) -> BTreeMap<Nibbles, Bytes>;
+}
+
+impl TrieBackend for HashBuilder {
+    fn with_proof_targets(targets: Vec<Nibbles>) -> Self {
+        HashBuilder::default().with_proof_retainer(targets)
+    }
+
+    fn insert_leaf(&mut self, key: Nibbles, value: &[u8]) {
+        self.add_leaf(key, value);
+    }
+
+    fn root(&mut self) -> B256 {
+        HashBuilder::root(self)
+    }
+
+    fn proof(&mut self) -> BTreeMap<Nibbles, Bytes> {
+        self.take_proofs()
+    }
+}
+
+/// Trie hashing its nodes with [crate::hasher]. Leaves are buffered, the
+/// nodes are built when the root is asked for
+#[derive(Debug, Default)]
+pub struct HasherTrie {
+    leaves: Vec<(Nibbles, Vec<u8>)>,
+    targets: Vec<Nibbles>,
+    proof: BTreeMap<Nibbles, Bytes>,
+}
+
+impl HasherTrie {
+    /// Encode the node holding `leaves`, which share their first `depth`
+    /// nibbles. Nodes on the path of a target are retained under the same
+    /// keys as [HashBuilder] does: their path, or the full key for leaves
+    fn encode_node(&mut self, leaves: &[(Nibbles, Vec<u8>)], depth: usize) -> Vec<u8> {
+        let first = &leaves[0].0;
+        let last = &leaves[leaves.len() - 1].0;
+
+        let (node, path) = if let [(key, value)] = leaves {
+            let path = key.slice(depth..).encode_path_leaf(true);
+            (
+                encode_list(&[&encode_string(&path), &encode_string(value)]),
+                key.clone(),
+            )
+        } else {
+            // Keys are sorted: the prefix of the first and last ones is
+            // shared by all
+            let shared = first.slice(depth..).common_prefix_length(&last[depth..]);
+            if shared > 0 {
+                let child = self.encode_node(leaves, depth + shared);
+                let path = first.slice(depth..depth + shared).encode_path_leaf(false);
+
+                (
+                    encode_list(&[&encode_string(&path), &child_reference(child)]),
+                    first.slice(..depth),
+                )
+            } else {
+                (self.encode_branch(leaves, depth), first.slice(..depth))
+            }
+        };
+
+        if self.targets.iter().any(|target| target.starts_with(&path)) {
+            self.proof.insert(path, node.clone().into());
+        }
+
+        node
+    }
+
+    /// Encode the branch node of `leaves`, which differ at nibble `depth`
+    fn encode_branch(&mut self, leaves: &[(Nibbles, Vec<u8>)], depth: usize) -> Vec<u8> {
+        // A key ending at the branch sorts first
+        let (value, mut rest) = match leaves {
+            [(key, value), rest @ ..] if key.len() == depth => (Some(value), rest),
+            _ => (None, leaves),
+        };
+
+        let mut items = Vec::with_capacity(17);
+        for nibble in 0..16 {
+            let count = rest
+                .iter()
+                .take_while(|(key, _)| key[depth] == nibble)
+                .count();
+            let (children, tail) = rest.split_at(count);
+            rest = tail;
+
+            items.push(if children.is_empty() {
+                vec![EMPTY_STRING_CODE]
+            } else {
+                child_reference(self.encode_node(children, depth + 1))
+            });
+        }
+        items.push(value.map_or(vec![EMPTY_STRING_CODE], |value| encode_string(value)));
+
+        encode_list(&items.iter().map(Vec::as_slice).collect::<Vec<_>>())
+    }
+}
+
+impl TrieBackend for HasherTrie {
+    fn with_proof_targets(targets: Vec<Nibbles>) -> Self {
+        Self {
+            targets,
+            ..Self::default()
+        }
+    }
+
+    fn insert_leaf(&mut self, key: Nibbles, value: &[u8]) {
+        self.leaves.push((key, value.to_vec()));
+    }
+
+    fn root(&mut self) -> B256 {
+        if self.leaves.is_empty() {
+            return EMPTY_ROOT_HASH;
+        }
+
+        let leaves = std::mem::take(&mut self.leaves);
+        let root = keccak256(self.encode_node(&leaves, 0));
+        self.leaves = leaves;
+
+        root
+    }
+
+    fn proof(&mut self) -> BTreeMap<Nibbles, Bytes> {
+        std::mem::take(&mut self.proof)
+    }
+}
+
+fn encode_string(bytes: &[u8]) -> Vec<u8> {
+    alloy_rlp::encode(bytes)
+}
+
+/// Encode a list of already encoded items
+fn encode_list(items: &[&[u8]]) -> Vec<u8> {
+    let payload_length = items.iter().map(|item| item.len()).sum();
+
+    let mut out = Vec::with_capacity(payload_length + 9);
+    alloy_rlp::Header {
+        list: true,
+        payload_length,
+    }
+    .encode(&mut out);
+    for item in items {
+        out.extend_from_slice(item);
+    }
+
+    out
+}
+
+/// Reference to a node from its parent: the node itself if shorter than 32
+/// bytes, its hash otherwise
+fn child_reference(node: Vec<u8>) -> Vec<u8> {
+    if node.len() < 32 {
+        node
+    } else {
+        encode_string(keccak256(node).as_slice())
+    }
+}
+
+/// Add `items` to `trie`, each keyed by the RLP encoding of its index.
+///
+/// Leaves are added in increasing key order, as required by [HashBuilder].
+pub fn add_ordered_leaves<T: Encodable, B: TrieBackend>(trie: &mut B, items: &[T]) {
+    let mut value_buffer: Vec<u8> = Vec::new();
+    let mut index_buffer: Vec<u8> = Vec::new();
+
+    let num_items = items.len();
+
+    for index in 0..num_items {
+        value_buffer.clear();
+        index_buffer.clear();
+
+        let index = index_for_rlp(index, num_items);
+
+        items[index].encode(&mut value_buffer);
+        index.encode(&mut index_buffer);
+
+        trie.insert_leaf(Nibbles::unpack(&index_buffer), &value_buffer);
+    }
+}
+
+/// Root of the trie of `items` keyed by their index
+pub fn ordered_trie_root<T: Encodable>(items: &[T]) -> B256 {
+    // Tries of blocks with at most one item are built directly
+    match items {
+        [] => EMPTY_ROOT_HASH,
+        [item] => single_leaf_root(item),
+        _ => ordered_trie_root_with::<DefaultTrie, T>(items),
+    }
+}
+
+/// Root of the trie with `item` as only leaf: the hash of the leaf node,
+/// even though it may be shorter than 32 bytes
+fn single_leaf_root<T: Encodable>(item: &T) -> B256 {
+    // Hex-prefix encoding of the even leaf path of `rlp(0) = 0x80`
+    let path = [0x20u8, 0x80];
+    let value = alloy_rlp::encode(item);
+
+    let mut leaf = Vec::new();
+    alloy_rlp::Header {
+        list: true,
+        payload_length: path.as_slice().length() + value.as_slice().length(),
+    }
+    .encode(&mut leaf);
+    path.as_slice().encode(&mut leaf);
+    value.as_slice().encode(&mut leaf);
+
+    keccak256(leaf)
+}
+
+/// Root of the trie of `items` keyed by their index, built with the
+/// backend `B`
+pub fn ordered_trie_root_with<B: TrieBackend, T: Encodable>(items: &[T]) -> B256 {
+    let mut trie = B::with_proof_targets(Vec::new());
+    add_ordered_leaves(&mut trie, items);
+
+    trie.root()
+}
+
+/// Build the proofs of the items at `indices` in the trie of `items`, in a
+/// single pass over the trie.
+///
+/// Duplicated indices are proven once. Fails if an index is out of range.
+pub fn ordered_trie_proofs<T: Encodable>(
+    items: &[T],
+    indices: &[usize],
+) -> Result<BTreeMap<usize, Vec<Bytes>>, RangeError> {
+    ordered_trie_proofs_with::<DefaultTrie, T>(items, indices)
+}
+
+/// Build the proofs of the items at `indices` as [ordered_trie_proofs], with
+/// the backend `B`
+pub fn ordered_trie_proofs_with<B: TrieBackend, T: Encodable>(
+    items: &[T],
+    indices: &[usize],
+) -> Result<BTreeMap<usize, Vec<Bytes>>, RangeError> {
+    if let Some(&index) = indices.iter().find(|index| **index >= items.len()) {
+        return Err(RangeError::IndexOutOfRange {
+            index,
+            len: items.len(),
+        });
+    }
+    let indices: BTreeSet<usize> = indices.iter().copied().collect();
+    let targets = indices
+        .iter()
+        .map(|index| Nibbles::unpack(alloy_rlp::encode(index)))
+        .collect();
+
+    let mut trie = B::with_proof_targets(targets);
+    add_ordered_leaves(&mut trie, items);
+
+    trie.root();
+    let nodes = trie.proof();
+
+    // The proof of a key are the nodes on its path, from the root
+    let proofs = indices
+        .into_iter()
+        .map(|index| {
+            let key = Nibbles::unpack(alloy_rlp::encode(index));
+            let proof = nodes
+                .iter()
+                .filter(|(path, _)| key.starts_with(path))
+                .map(|(_, node)| node.clone())
+                .collect();
+
+            (index, proof)
+        })
+        .collect();
+
+    Ok(proofs)
+}
+
+/// Build the transactions trie of a block one transaction at a time, so each
+/// can be dropped once added.
+///
+/// Transactions must be added in trie key order, the one of
+/// [index_for_rlp]: [TransactionTrieStreamer::next_index] tells which
+/// transaction comes next.
+#[derive(Debug)]
+pub struct TransactionTrieStreamer {
+    trie: DefaultTrie,
+    num_transactions: usize,
+    added: usize,
+    value_buffer: Vec<u8>,
+    index_buffer: Vec<u8>,
+}
+
+impl TransactionTrieStreamer {
+    pub fn new(num_transactions: usize) -> Self {
+        Self {
+            trie: DefaultTrie::with_proof_targets(Vec::new()),
+            num_transactions,
+            added: 0,
+            value_buffer: Vec::new(),
+            index_buffer: Vec::new(),
+        }
+    }
+
+    /// Index of the transaction expected by the next [Self::add_leaf], `None`
+    /// once all of them were added
+    pub fn next_index(&self) -> Option<usize> {
+        (self.added < self.num_transactions)
+            .then(|| index_for_rlp(self.added, self.num_transactions))
+    }
+
+    /// Add the transaction at [Self::next_index]. Fails once all the
+    /// transactions were added.
+    pub fn add_leaf<T: Encodable>(&mut self, transaction: &T) -> Result<(), RangeError> {
+        let index = self.next_index().ok_or(RangeError::IndexOutOfRange {
+            index: self.added,
+            len: self.num_transactions,
+        })?;
+
+        self.value_buffer.clear();
+        self.index_buffer.clear();
+
+        transaction.encode(&mut self.value_buffer);
+        index.encode(&mut self.index_buffer);
+
+        self.trie
+            .insert_leaf(Nibbles::unpack(&self.index_buffer), &self.value_buffer);
+        self.added += 1;
+
+        Ok(())
+    }
+
+    /// Root of the trie, `None` while some transactions were not added
+    pub fn root(mut self) -> Option<B256> {
+        (self.added == self.num_transactions).then(|| self.trie.root())
+    }
+}
+
+/// Build the receipts trie of a block from its receipts in block order, as
+/// they are read from a response, so each can be dropped once added.
+///
+/// Only the first receipt is kept: its key, `0x80`, sorts after the ones of
+/// the receipts 1 to 127.
+#[derive(Debug, Default)]
+pub struct ReceiptTrieStreamer {
+    trie: DefaultTrie,
+    added: usize,
+    first: Option<Vec<u8>>,
+    value_buffer: Vec<u8>,
+    index_buffer: Vec<u8>,
+}
+
+impl ReceiptTrieStreamer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the receipt following the ones already added
+    pub fn add_leaf<T: Encodable>(&mut self, receipt: &T) {
+        let index = self.added;
+        self.added += 1;
+
+        if index == 0 {
+            self.first = Some(alloy_rlp::encode(receipt));
+            return;
+        }
+        if index == 0x80 {
+            self.add_first();
+        }
+
+        self.value_buffer.clear();
+        receipt.encode(&mut self.value_buffer);
+        self.insert(index);
+    }
+
+    fn add_first(&mut self) {
+        if let Some(first) = self.first.take() {
+            self.value_buffer = first;
+            self.insert(0);
+        }
+    }
+
+    fn insert(&mut self, index: usize) {
+        self.index_buffer.clear();
+        index.encode(&mut self.index_buffer);
+
+        self.trie
+            .insert_leaf(Nibbles::unpack(&self.index_buffer), &self.value_buffer);
+    }
+
+    /// Root of the trie of the receipts added so far
+    pub fn root(mut self) -> B256 {
+        self.add_first();
+
+        self.trie.root()
+    }
+}
+
+/// Build a trie from items received in any order, as transactions from
+/// peers. Leaves are buffered until the items at every index are known.
+#[derive(Debug)]
+pub struct IncrementalTrie {
+    leaves: Vec<Option<Vec<u8>>>,
+    missing: usize,
+}
+
+impl IncrementalTrie {
+    pub fn new(num_items: usize) -> Self {
+        Self {
+            leaves: vec![None; num_items],
+            missing: num_items,
+        }
+    }
+
+    /// Add the item at `index`, replacing the one already there. Fails if
+    /// `index` is not below the number of items of the trie.
+    pub fn insert<T: Encodable>(&mut self, index: usize, item: &T) -> Result<(), RangeError> {
+        let len = self.leaves.len();
+        let leaf = self
+            .leaves
+            .get_mut(index)
+            .ok_or(RangeError::IndexOutOfRange { index, len })?;
+        if leaf.is_none() {
+            self.missing -= 1;
+        }
+
+        *leaf = Some(alloy_rlp::encode(item));
+
+        Ok(())
+    }
+
+    /// Whether the items at every index were inserted
+    pub fn is_complete(&self) -> bool {
+        self.missing == 0
+    }
+
+    /// Root of the trie, `None` while some items are missing
+    pub fn root(&self) -> Option<B256> {
+        if !self.is_complete() {
+            return None;
+        }
+
+        let items: Vec<Encoded<'_>> = self
+            .leaves
+            .iter()
+            .map(|leaf| Encoded(leaf.as_deref().unwrap_or_default()))
+            .collect();
+
+        Some(ordered_trie_root(&items))
+    }
+
+    /// Check the root against `expected`, once all the items are known
+    pub fn verify(&self, expected: B256) -> bool {
+        self.root() == Some(expected)
+    }
+}
+
+/// Item already RLP encoded, written as is
+struct Encoded<'a>(&'a [u8]);
+
+impl Encodable for Encoded<'_> {
+    fn encode(&self, out: &mut dyn alloy_rlp::BufMut) {
+        out.put_slice(self.0);
+    }
+
+    fn length(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::{address, b256, uint, TxKind, U256};
+
+    use super::*;
+    use crate::{
+        receipt::{tests::receipt_fixture, VerifiedReceipt},
+        transaction::{tests::legacy_fixture, Signature, TxLegacy, VerifiedTransaction},
+    };
+
+    fn transaction_at(index: usize) -> VerifiedTransaction {
+        let mut txn = legacy_fixture();
+        txn.nonce = index as u64;
+
+        VerifiedTransaction::Legacy(txn)
+    }
+
+    #[test]
+    fn should_match_sorted_keys_root() {
+        // Cross the `0x7f` boundary where the index ordering changes
+        let items: Vec<u64> = (0..200).map(|i| i * 1_000_003).collect();
+
+        let mut leaves = BTreeMap::new();
+        for (index, item) in items.iter().enumerate() {
+            leaves.insert(
+                Nibbles::unpack(alloy_rlp::encode(index)),
+                alloy_rlp::encode(item),
+            );
+        }
+
+        let mut trie = HashBuilder::default();
+        for (key, value) in &leaves {
+            trie.add_leaf(key.clone(), value);
+        }
+
+        assert_eq!(ordered_trie_root(&items), trie.root());
+    }
+
+    /// Backend sorting the leaves itself, accepting them in any order
+    struct SortingBackend {
+        leaves: BTreeMap<Nibbles, Vec<u8>>,
+        targets: Vec<Nibbles>,
+        proof: BTreeMap<Nibbles, Bytes>,
+    }
+
+    impl TrieBackend for SortingBackend {
+        fn with_proof_targets(targets: Vec<Nibbles>) -> Self {
+            Self {
+                leaves: BTreeMap::new(),
+                targets,
+                proof: BTreeMap::new(),
+            }
+        }
+
+        fn insert_leaf(&mut self, key: Nibbles, value: &[u8]) {
+            self.leaves.insert(key, value.to_vec());
+        }
+
+        fn root(&mut self) -> B256 {
+            let mut trie = HashBuilder::with_proof_targets(self.targets.clone());
+            for (key, value) in &self.leaves {
+                trie.add_leaf(key.clone(), value);
+            }
+
+            let root = trie.root();
+            self.proof = trie.take_proofs();
+            root
+        }
+
+        fn proof(&mut self) -> BTreeMap<Nibbles, Bytes> {
+            std::mem::take(&mut self.proof)
+        }
+    }
+
+    #[test]
+    fn should_build_tiny_tries_directly() {
+        let none: [VerifiedTransaction; 0] = [];
+        assert_eq!(ordered_trie_root(&none), EMPTY_ROOT_HASH);
+        assert_eq!(
+            ordered_trie_root(&none),
+            ordered_trie_root_with::<HashBuilder, _>(&none)
+        );
+
+        // The only transaction of block 3 of a development chain, from the
+        // tests of `ethers-core`
+        let txn = VerifiedTransaction::Legacy(TxLegacy {
+            nonce: 2,
+            gas_price: 20_000_000_000,
+            gas_limit: 90000,
+            to: TxKind::Call(address!("dca8ce283150ab773bcbeb8d38289bdb5661de1e")),
+            value: U256::ZERO,
+            data: Bytes::new(),
+            signature: Signature {
+                v: U256::from(0x25),
+                r: uint!(0x19f2694eb9113656dbea0b925e2e7ceb43df83e601c4116aee9c0dd99130be88_U256),
+                s: uint!(0x73e5764b324a4f7679d890a198ba658ba1c8cd36983ff9797e10b1b89dbb448e_U256),
+            },
+            receipt: VerifiedReceipt::default(),
+        });
+        assert_eq!(
+            txn.hash(),
+            b256!("c3c5f700243de37ae986082fd2af88d2a7c2752a0c0f7b9d6ac47c729d45e067")
+        );
+
+        let single = [txn];
+        assert_eq!(
+            ordered_trie_root(&single),
+            b256!("7270c1c4440180f2bd5215809ee3d545df042b67329499e1ab97eb759d31610d")
+        );
+        assert_eq!(
+            ordered_trie_root(&single),
+            ordered_trie_root_with::<HashBuilder, _>(&single)
+        );
+    }
+
+    #[test]
+    fn should_build_tries_with_backend() {
+        let transactions: Vec<VerifiedTransaction> = (0..150).map(transaction_at).collect();
+        let root = ordered_trie_root(&transactions);
+        let proofs = ordered_trie_proofs(&transactions, &[0, 1, 127, 128]).unwrap();
+
+        assert_eq!(
+            ordered_trie_root_with::<HashBuilder, _>(&transactions),
+            root
+        );
+        assert_eq!(
+            ordered_trie_root_with::<SortingBackend, _>(&transactions),
+            root
+        );
+        assert_eq!(
+            ordered_trie_proofs_with::<SortingBackend, _>(&transactions, &[0, 1, 127, 128]),
+            Ok(proofs)
+        );
+    }
+
+    #[test]
+    fn should_match_hash_builder_with_hasher_trie() {
+        for num_transactions in [1, 2, 127, 128, 150] {
+            let transactions: Vec<VerifiedTransaction> =
+                (0..num_transactions).map(transaction_at).collect();
+            let indices = [0, num_transactions / 2, num_transactions - 1];
+
+            assert_eq!(
+                ordered_trie_root_with::<HasherTrie, _>(&transactions),
+                ordered_trie_root_with::<HashBuilder, _>(&transactions)
+            );
+            assert_eq!(
+                ordered_trie_proofs_with::<HasherTrie, _>(&transactions, &indices),
+                ordered_trie_proofs_with::<HashBuilder, _>(&transactions, &indices)
+            );
+        }
+
+        // Short leaves are embedded in their branch
+        let items: Vec<u8> = (0..16).collect();
+        assert_eq!(
+            ordered_trie_root_with::<HasherTrie, _>(&items),
+            ordered_trie_root_with::<HashBuilder, _>(&items)
+        );
+        assert_eq!(
+            ordered_trie_proofs_with::<HasherTrie, _>(&items, &[0, 7]),
+            ordered_trie_proofs_with::<HashBuilder, _>(&items, &[0, 7])
+        );
+    }
+
+    #[test]
+    fn should_stream_transactions_trie() {
+        let num_transactions = 150;
+        let transactions: Vec<VerifiedTransaction> =
+            (0..num_transactions).map(transaction_at).collect();
+
+        let mut streamer = TransactionTrieStreamer::new(num_transactions);
+        while let Some(index) = streamer.next_index() {
+            // Decoded on demand and dropped right after
+            streamer.add_leaf(&transaction_at(index)).unwrap();
+        }
+        assert_eq!(
+            streamer.add_leaf(&transaction_at(0)),
+            Err(RangeError::IndexOutOfRange {
+                index: num_transactions,
+                len: num_transactions
+            })
+        );
+
+        assert_eq!(streamer.root(), Some(ordered_trie_root(&transactions)));
+
+        // Missing transactions
+        let mut streamer = TransactionTrieStreamer::new(num_transactions);
+        streamer.add_leaf(&transaction_at(1)).unwrap();
+        assert_eq!(streamer.root(), None);
+    }
+
+    #[test]
+    fn should_build_trie_from_shuffled_transactions() {
+        let num_transactions = 150;
+        let transactions: Vec<VerifiedTransaction> =
+            (0..num_transactions).map(transaction_at).collect();
+        let root = ordered_trie_root(&transactions);
+
+        let mut trie = IncrementalTrie::new(num_transactions);
+        // 7 is coprime with 150, every index is visited once
+        for index in (0..num_transactions).map(|i| i * 7 % num_transactions) {
+            assert_eq!(trie.root(), None);
+            trie.insert(index, &transactions[index]).unwrap();
+        }
+
+        assert!(trie.is_complete());
+        assert!(trie.verify(root));
+
+        trie.insert(0, &transactions[1]).unwrap();
+        assert!(!trie.verify(root));
+
+        assert_eq!(
+            trie.insert(num_transactions, &transactions[0]),
+            Err(RangeError::IndexOutOfRange {
+                index: num_transactions,
+                len: num_transactions
+            })
+        );
+    }
+
+    #[test]
+    fn should_build_incremental_trie_of_few_items() {
+        assert_eq!(IncrementalTrie::new(0).root(), Some(EMPTY_ROOT_HASH));
+
+        let mut trie = IncrementalTrie::new(1);
+        trie.insert(0, &transaction_at(0)).unwrap();
+        assert_eq!(trie.root(), Some(ordered_trie_root(&[transaction_at(0)])));
+    }
+
+    #[test]
+    fn should_stream_receipts_trie_in_block_order() {
+        for num_receipts in [0, 1, 2, 127, 128, 129, 300] {
+            let receipts: Vec<VerifiedReceipt> = (0..num_receipts)
+                .map(|index| {
+                    let mut receipt = receipt_fixture(Some(2));
+                    receipt.cumulative_gas_used = U256::from(index * 21000);
+                    receipt
+                })
+                .collect();
+
+            let mut streamer = ReceiptTrieStreamer::new();
+            for receipt in &receipts {
+                streamer.add_leaf(receipt);
+            }
+
+            assert_eq!(streamer.root(), ordered_trie_root(&receipts));
+        }
+    }
+}
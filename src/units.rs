@@ -0,0 +1,74 @@
+//! Parsing of ether amounts, for transactions built from CLI arguments or
+//! CSV rather than RPC responses
+use alloy_primitives::U256;
+
+use crate::error::ParseError;
+
+/// Decimals of ether: 1 ether is 10^18 wei
+pub const ETHER_DECIMALS: usize = 18;
+
+/// Parse an amount of wei, in decimal or `0x` prefixed hex
+pub fn parse_wei(s: &str) -> Result<U256, ParseError> {
+    let invalid = || ParseError::InvalidAmount(s.to_owned());
+
+    let (digits, radix) = match s.strip_prefix("0x") {
+        Some(hex) => (hex, 16),
+        None => (s, 10),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(invalid());
+    }
+
+    U256::from_str_radix(digits, radix as u64).map_err(|_| invalid())
+}
+
+/// Parse an amount of ether in decimal, with at most 18 decimals, as wei
+pub fn parse_ether(s: &str) -> Result<U256, ParseError> {
+    let invalid = || ParseError::InvalidAmount(s.to_owned());
+
+    let (integer, fraction) = match s.split_once('.') {
+        Some((_, "")) => return Err(invalid()),
+        Some(parts) => parts,
+        None => (s, ""),
+    };
+    let is_decimal = |digits: &str| digits.chars().all(|c| c.is_ascii_digit());
+    if integer.is_empty()
+        || fraction.len() > ETHER_DECIMALS
+        || !is_decimal(integer)
+        || !is_decimal(fraction)
+    {
+        return Err(invalid());
+    }
+
+    let wei = format!("{integer}{fraction:0<ETHER_DECIMALS$}");
+
+    U256::from_str_radix(&wei, 10).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::uint;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_amounts() {
+        let expected = uint!(1500000000000000000_U256);
+
+        assert_eq!(parse_wei("1500000000000000000"), Ok(expected));
+        assert_eq!(parse_wei("0x14d1120d7b160000"), Ok(expected));
+        assert_eq!(parse_ether("1.5"), Ok(expected));
+        assert_eq!(parse_ether("2"), Ok(uint!(2000000000000000000_U256)));
+        assert_eq!(parse_ether("0.000000000000000001"), Ok(U256::from(1)));
+
+        for invalid in ["", "0x", "1.5", "-1", "1_000"] {
+            assert_eq!(
+                parse_wei(invalid),
+                Err(ParseError::InvalidAmount(invalid.to_owned()))
+            );
+        }
+        for invalid in ["", ".5", "1.", "1.0000000000000000001", "1e18"] {
+            assert!(parse_ether(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -1,4 +1,5 @@
 //! Utilities functions
+use crate::error::DecodeError;
 
 /// First encode index from 1..127 and then 0.
 ///
@@ -14,3 +15,49 @@ pub const fn index_for_rlp(i: usize, len: usize) -> usize {
         i + 1
     }
 }
+
+/// Take the payload of the RLP list at the start of `buf`, advancing `buf`
+/// past the list.
+pub fn take_list_payload<'a>(buf: &mut &'a [u8]) -> Result<&'a [u8], alloy_rlp::Error> {
+    let header = alloy_rlp::Header::decode(buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString);
+    }
+    if buf.len() < header.payload_length {
+        return Err(alloy_rlp::Error::InputTooShort);
+    }
+
+    let (payload, rest) = buf.split_at(header.payload_length);
+    *buf = rest;
+
+    Ok(payload)
+}
+
+/// Decode a trailing field of a list payload, `None` if the payload is over
+pub fn decode_optional<T: alloy_rlp::Decodable>(
+    payload: &mut &[u8],
+) -> Result<Option<T>, alloy_rlp::Error> {
+    if payload.is_empty() {
+        return Ok(None);
+    }
+
+    T::decode(payload).map(Some)
+}
+
+/// Check that no bytes follow a decoded item, as in strict decoding
+pub fn ensure_no_trailing_bytes(buf: &[u8]) -> Result<(), DecodeError> {
+    if buf.is_empty() {
+        Ok(())
+    } else {
+        Err(DecodeError::TrailingBytes(buf.len()))
+    }
+}
+
+/// Check that the payload of a list has been entirely consumed
+pub fn ensure_consumed(payload: &[u8]) -> Result<(), alloy_rlp::Error> {
+    if payload.is_empty() {
+        Ok(())
+    } else {
+        Err(alloy_rlp::Error::UnexpectedLength)
+    }
+}